@@ -0,0 +1,339 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The answer to one part of a puzzle.
+///
+/// Integer answers compare by value regardless of their width or signedness,
+/// so a solver can return whichever integer type is natural for it.
+///
+/// ```
+/// # use adventofcode2023::answer::Answer;
+/// assert_eq!(Answer::from(42u64), 42);
+/// assert_eq!(Answer::from(42u64), Answer::from(42i128));
+/// assert_eq!(Answer::from("abc"), "abc");
+/// assert_eq!(Answer::from(-7i64).to_string(), "-7");
+/// ```
+#[derive(Clone, Debug)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Text(String),
+}
+
+/// An answer that can be written in a constant, like the answers a puzzle
+/// gives for its examples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StaticAnswer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Text(&'static str),
+}
+
+impl From<StaticAnswer> for Answer {
+    fn from(value: StaticAnswer) -> Self {
+        match value {
+            StaticAnswer::I64(n) => Self::I64(n),
+            StaticAnswer::U64(n) => Self::U64(n),
+            StaticAnswer::I128(n) => Self::I128(n),
+            StaticAnswer::U128(n) => Self::U128(n),
+            StaticAnswer::Text(s) => Self::from(s),
+        }
+    }
+}
+
+/// A sign-normalized view of an integer answer used for comparisons.
+/// Every negative value orders before every non-negative one.
+#[derive(PartialEq, PartialOrd)]
+enum Integer {
+    Negative(i128),
+    NonNegative(u128),
+}
+
+impl Answer {
+    fn as_integer(&self) -> Option<Integer> {
+        let integer = match *self {
+            Self::I64(n) if n < 0 => Integer::Negative(n as i128),
+            Self::I64(n) => Integer::NonNegative(n as u128),
+            Self::U64(n) => Integer::NonNegative(n as u128),
+            Self::I128(n) if n < 0 => Integer::Negative(n),
+            Self::I128(n) => Integer::NonNegative(n as u128),
+            Self::U128(n) => Integer::NonNegative(n),
+            Self::Text(_) => return None,
+        };
+
+        Some(integer)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::I64(n) => write!(f, "{}", n),
+            Self::U64(n) => write!(f, "{}", n),
+            Self::I128(n) => write!(f, "{}", n),
+            Self::U128(n) => write!(f, "{}", n),
+            Self::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (a, b) => match (a.as_integer(), b.as_integer()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Integer answers are ordered by value. Text answers are only ordered
+/// against an equal text answer.
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => (a == b).then_some(Ordering::Equal),
+            (a, b) => a.as_integer()?.partial_cmp(&b.as_integer()?),
+        }
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        *self == Self::I64(*other)
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        matches!(self, Self::Text(s) if s == other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Answer {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Self::I64(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::U64(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::U64(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::U64(value as u64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        Self::I128(value)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Self::U128(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+/// Integers are written as numbers when they fit in 64 bits and as strings
+/// of digits otherwise, since JSON readers commonly lose precision on wider
+/// numbers. Such strings are read back as integers.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::I64(n) => serializer.serialize_i64(*n),
+            Self::U64(n) => serializer.serialize_u64(*n),
+            Self::I128(n) => match (i64::try_from(*n), u64::try_from(*n)) {
+                (Ok(n), _) => serializer.serialize_i64(n),
+                (_, Ok(n)) => serializer.serialize_u64(n),
+                _ => serializer.collect_str(n),
+            },
+            Self::U128(n) => match u64::try_from(*n) {
+                Ok(n) => serializer.serialize_u64(n),
+                Err(_) => serializer.collect_str(n),
+            },
+            Self::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+/// Parses an integer that was written as a string because it does not fit
+/// in 64 bits.
+fn wide_integer(value: &str) -> Option<Answer> {
+    match value.parse::<u128>() {
+        Ok(n) if n > u64::MAX as u128 => Some(Answer::U128(n)),
+        Ok(_) => None,
+        Err(_) => value
+            .parse::<i128>()
+            .ok()
+            .filter(|&n| n < i64::MIN as i128)
+            .map(Answer::I128),
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer or a string")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Answer, E> {
+        Ok(Answer::I64(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Answer, E> {
+        Ok(Answer::U64(value))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Answer, E> {
+        Ok(Answer::I128(value))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<Answer, E> {
+        Ok(Answer::U128(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Answer, E> {
+        Ok(wide_integer(value).unwrap_or_else(|| Answer::from(value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_equality_ignores_width() {
+        assert_eq!(Answer::from(5i64), Answer::from(5u64));
+        assert_eq!(Answer::from(5u128), Answer::from(5i128));
+        assert_eq!(Answer::from(-5i64), Answer::from(-5i128));
+        assert_ne!(Answer::from(-5i64), Answer::from(5u64));
+        assert_ne!(Answer::from(u128::MAX), Answer::from(-1i128));
+    }
+
+    #[test]
+    fn test_text_is_never_an_integer() {
+        assert_ne!(Answer::from("5"), Answer::from(5i64));
+        assert_eq!(Answer::from("LLR"), "LLR");
+        assert_eq!(Answer::from("LLR"), "LLR".to_string());
+    }
+
+    #[test]
+    fn test_static_answer() {
+        assert_eq!(
+            Answer::from(StaticAnswer::U128(u128::MAX)),
+            Answer::from(u128::MAX)
+        );
+        assert_eq!(Answer::from(StaticAnswer::I64(7)), 7);
+        assert_eq!(Answer::from(StaticAnswer::Text("LLR")), "LLR");
+    }
+
+    #[test]
+    fn test_ordering() {
+        let (negative, small, large) = (
+            Answer::from(-5i64),
+            Answer::from(3u64),
+            Answer::from(u128::MAX),
+        );
+
+        assert!(negative < small);
+        assert!(large > Answer::from(i128::MAX));
+        assert_eq!(Answer::from("a").partial_cmp(&Answer::from("b")), None);
+        assert_eq!(Answer::from("5").partial_cmp(&Answer::from(5i64)), None);
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let answers = vec![
+            Answer::from(-3i64),
+            Answer::from(167409079868000u64),
+            Answer::from("BGJ"),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+
+        assert_eq!(json, r#"[-3,167409079868000,"BGJ"]"#);
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, answers);
+    }
+
+    #[test]
+    fn test_wide_integers_round_trip() {
+        let answers = vec![
+            Answer::from(u128::MAX),
+            Answer::from(i128::MIN),
+            Answer::from(-3i128),
+            Answer::from("12"),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+
+        assert_eq!(
+            json,
+            format!(r#"["{}","{}",-3,"12"]"#, u128::MAX, i128::MIN)
+        );
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+
+        assert!(matches!(parsed[0], Answer::U128(u128::MAX)));
+        assert!(matches!(parsed[1], Answer::I128(i128::MIN)));
+        assert_eq!(parsed, answers);
+    }
+}
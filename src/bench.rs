@@ -0,0 +1,266 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{Job, Outcome};
+
+/// Where baselines are kept unless another directory is given.
+pub const DEFAULT_BASELINE_DIR: &str = "baselines";
+
+/// How many percent slower than its baseline a solve may get before it is
+/// flagged, unless another threshold is given.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Summary statistics over repeated timings of the same solve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Computes statistics over the samples. Returns `None` if there are none.
+    ///
+    /// The median of an even number of samples is the mean of the middle two,
+    /// and the 95th percentile uses the nearest-rank method.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        let total = sorted.iter().sum::<Duration>();
+        let mean = total / runs as u32;
+
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        let rank = (runs as f64 * 0.95).ceil() as usize;
+        let p95 = sorted[rank.max(1) - 1];
+
+        let mean_secs = mean.as_secs_f64();
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Some(Self {
+            runs,
+            min: sorted[0],
+            mean,
+            median,
+            p95,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Solves a job `warmup` times without recording, then `iterations` times
+/// recording how long each solve took.
+///
+/// Fails with the solver's error message if any of the solves fail.
+pub fn bench(job: &Job, input: &str, warmup: usize, iterations: usize) -> Result<Stats, String> {
+    let mut samples = Vec::with_capacity(iterations);
+
+    for i in 0..(warmup + iterations) {
+        match job.run(input).outcome {
+            Outcome::Solved { timings, .. } => {
+                if i >= warmup {
+                    samples.push(timings.total());
+                }
+            }
+            failed => return Err(failed.to_string()),
+        }
+    }
+
+    Stats::from_samples(&samples).ok_or_else(|| "no iterations were run".to_string())
+}
+
+/// A saved benchmark of one part of a day, kept in its own file so parts can
+/// be re-baselined independently.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub day: u8,
+    pub part: u8,
+    pub runs: usize,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Baseline {
+    pub fn new(day: u8, part: u8, stats: &Stats) -> Self {
+        Self {
+            day,
+            part,
+            runs: stats.runs,
+            median_ns: stats.median.as_nanos() as u64,
+            mean_ns: stats.mean.as_nanos() as u64,
+        }
+    }
+
+    /// The file holding the baseline for one part of a day.
+    pub fn path(dir: &str, day: u8, part: u8) -> PathBuf {
+        Path::new(dir).join(format!("{:02}-{}.json", day, part))
+    }
+
+    /// Loads the baseline for one part of a day, if one was saved.
+    pub fn load(dir: &str, day: u8, part: u8) -> Result<Option<Self>, String> {
+        let path = Self::path(dir, day, part);
+
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("could not read {}: {}", path.display(), e)),
+        };
+
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("invalid baseline {}: {}", path.display(), e))
+    }
+
+    /// Writes the baseline into `dir`, replacing any earlier one for the same
+    /// part, and returns the path it was written to.
+    pub fn save(&self, dir: &str) -> Result<PathBuf, String> {
+        let path = Self::path(dir, self.day, self.part);
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+
+        std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&path, json + "\n"))
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+
+        Ok(path)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    /// How the median of a new benchmark compares with this baseline.
+    pub fn compare(&self, stats: &Stats) -> Comparison {
+        Comparison {
+            baseline: self.median(),
+            current: stats.median,
+        }
+    }
+}
+
+/// A new median timing measured against a baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// The change from the baseline in percent, positive when slower.
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_nanos() as f64;
+
+        if baseline == 0.0 {
+            return 0.0;
+        }
+
+        (self.current.as_nanos() as f64 - baseline) / baseline * 100.0
+    }
+
+    /// Whether the solve got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_stats_odd_samples() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 3])).unwrap();
+
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.std_dev.as_micros(), 1632);
+    }
+
+    #[test]
+    fn test_stats_even_samples() {
+        let samples = millis(&(1..=20).collect::<Vec<_>>());
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.median, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_bench_counts_only_measured_iterations() {
+        let registry = crate::registry();
+        let job = Job {
+            solver: registry.get(6).unwrap(),
+            part: 1,
+        };
+
+        let stats = bench(&job, "Time: 7 15 30\nDistance: 9 40 200", 2, 5).unwrap();
+
+        assert_eq!(stats.runs, 5);
+        assert!(stats.min <= stats.median && stats.median <= stats.p95);
+    }
+
+    #[test]
+    fn test_comparison() {
+        let comparison = Comparison {
+            baseline: Duration::from_millis(200),
+            current: Duration::from_millis(250),
+        };
+
+        assert_eq!(comparison.change(), 25.0);
+        assert!(comparison.is_regression(10.0));
+        assert!(!comparison.is_regression(30.0));
+
+        let faster = Comparison {
+            baseline: Duration::from_millis(200),
+            current: Duration::from_millis(150),
+        };
+
+        assert_eq!(faster.change(), -25.0);
+        assert!(!faster.is_regression(0.0));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let dir = std::env::temp_dir().join(format!("aoc-baselines-{}", std::process::id()));
+        let dir = dir.to_str().unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+
+        let stats = Stats::from_samples(&millis(&[5, 1, 3])).unwrap();
+        let baseline = Baseline::new(6, 2, &stats);
+
+        assert_eq!(Baseline::load(dir, 6, 2), Ok(None));
+        assert_eq!(baseline.save(dir), Ok(Path::new(dir).join("06-2.json")));
+        assert_eq!(Baseline::load(dir, 6, 2), Ok(Some(baseline.clone())));
+        assert_eq!(baseline.median(), Duration::from_millis(3));
+    }
+}
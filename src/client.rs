@@ -0,0 +1,257 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::submit::Reply;
+
+/// Where puzzles are fetched from unless another base URL is given.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2023";
+
+/// The environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Reads the session token from a file, or from `AOC_SESSION` if no file
+/// was given.
+pub fn session(file: Option<&str>) -> Result<String, String> {
+    let token = match file {
+        Some(file) => std::fs::read_to_string(file)
+            .map_err(|e| format!("could not read session file {}: {}", file, e))?,
+        None => std::env::var(SESSION_VAR).map_err(|_| {
+            format!(
+                "no session token: pass --session-file or set {}",
+                SESSION_VAR
+            )
+        })?,
+    };
+
+    let token = token.trim();
+
+    if token.is_empty() {
+        return Err("the session token is empty".to_string());
+    }
+
+    Ok(token.to_string())
+}
+
+/// An authenticated connection to the puzzle site.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Downloads the puzzle input for a day.
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/day/{}/input", self.base_url, day);
+
+        match self.agent.get(&url).set("Cookie", &self.cookie()).call() {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| format!("could not read the input for day {}: {}", day, e)),
+            Err(ureq::Error::Status(404, _)) => {
+                Err(format!("the input for day {} is not available yet", day))
+            }
+            Err(ureq::Error::Status(status, _)) => Err(format!(
+                "could not download the input for day {}: the server responded with {}",
+                day, status
+            )),
+            Err(e) => Err(format!("could not reach {}: {}", url, e)),
+        }
+    }
+
+    /// Submits the answer to one part of a day and reads the site's reply.
+    pub fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<Reply, String> {
+        let url = format!("{}/day/{}/answer", self.base_url, day);
+        let level = part.to_string();
+        let answer = answer.to_string();
+
+        match self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", &answer)])
+        {
+            Ok(response) => response
+                .into_string()
+                .map(|body| Reply::parse(&body))
+                .map_err(|e| format!("could not read the reply for day {}: {}", day, e)),
+            Err(ureq::Error::Status(status, _)) => Err(format!(
+                "could not submit the answer for day {} part {}: the server responded with {}",
+                day, part, status
+            )),
+            Err(e) => Err(format!("could not reach {}: {}", url, e)),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Whether `fetch` had to download the input.
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Makes sure the input for a day is stored at `path`.
+///
+/// An input that is already on disk is never requested again, so `connect`
+/// is only called when there is something to download. The file is written
+/// in full before it is moved into place, so a failed download never leaves
+/// a partial input behind.
+pub fn fetch(
+    day: u8,
+    path: &str,
+    connect: impl FnOnce() -> Result<Client, String>,
+) -> Result<Fetched, String> {
+    let path = Path::new(path);
+
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = connect()?.input(day)?;
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+    }
+
+    let partial = path.with_extension("part");
+
+    std::fs::write(&partial, input)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use tiny_http::{Method, Response, Server};
+
+    use crate::submit::Feedback;
+
+    /// Serves inputs like the puzzle site would, counting every request.
+    fn stand_in() -> (String, Arc<AtomicUsize>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2023", server.server_addr());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+
+                let authorized = request
+                    .headers()
+                    .iter()
+                    .any(|header| header.field.equiv("Cookie") && header.value == "session=abc");
+
+                let response = match (request.method(), request.url()) {
+                    _ if !authorized => Response::from_string("log in").with_status_code(400),
+                    (Method::Get, "/2023/day/6/input") => {
+                        Response::from_string("Time: 7\nDistance: 9\n")
+                    }
+                    (Method::Post, "/2023/day/6/answer") if body == "level=1&answer=288" => {
+                        Response::from_string("<p>That's the right answer!</p>")
+                    }
+                    (Method::Post, "/2023/day/6/answer") => Response::from_string(
+                        "<p>That's not the right answer; your answer is too low.</p>",
+                    ),
+                    _ => Response::from_string("not found").with_status_code(404),
+                };
+
+                request.respond(response).unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn scratch(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        dir.join("06.txt").to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_fetch_caches_input() {
+        let (base_url, requests) = stand_in();
+        let path = scratch("cache");
+        let connect = || Ok(Client::new(&base_url, "abc".to_string()));
+
+        assert_eq!(fetch(6, &path, connect), Ok(Fetched::Downloaded));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "Time: 7\nDistance: 9\n"
+        );
+
+        assert_eq!(fetch(6, &path, connect), Ok(Fetched::Cached));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, _) = stand_in();
+        let path = scratch("errors");
+
+        let missing = fetch(7, &path, || Ok(Client::new(&base_url, "abc".to_string())));
+        let unauthorized = fetch(6, &path, || Ok(Client::new(&base_url, "xyz".to_string())));
+
+        assert_eq!(
+            missing,
+            Err("the input for day 7 is not available yet".to_string())
+        );
+        assert!(unauthorized.unwrap_err().contains("responded with 400"));
+        assert!(!Path::new(&path).exists());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, _) = stand_in();
+        let client = Client::new(&base_url, "abc".to_string());
+
+        let right = client.submit(6, 1, &Answer::from(288i64)).unwrap();
+        let low = client.submit(6, 1, &Answer::from(200i64)).unwrap();
+
+        assert_eq!(right.feedback, Feedback::Correct);
+        assert_eq!(low.feedback, Feedback::TooLow);
+        assert!(client.submit(9, 1, &Answer::from(1i64)).is_err());
+    }
+
+    #[test]
+    fn test_session_from_file() {
+        let path = std::env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        std::fs::write(&path, "abc\n").unwrap();
+
+        assert_eq!(session(path.to_str()), Ok("abc".to_string()));
+        assert!(session(Some("missing-session")).is_err());
+    }
+}
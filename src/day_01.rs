@@ -1,22 +1,86 @@
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
 use std::collections::HashMap;
 
-pub fn part_one(input: &str) -> i64 {
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 1, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        name: "digits",
+        input: r#"1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet"#,
+        answers: &[(1, StaticAnswer::I64(142))],
+    },
+    Example {
+        name: "spelled digits",
+        input: r#"two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen"#,
+        answers: &[(2, StaticAnswer::I64(281))],
+    },
+];
+
+fn parse(input: &str) -> Result<Vec<&str>> {
+    Ok(input.lines().collect())
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+fn no_digit(line: usize) -> Error {
+    Error::missing(format!("line {} has no digit", line + 1))
+}
+
+fn solve_one(lines: Vec<&str>) -> Result<Answer> {
     let mut total = 0;
 
-    for line in input.lines() {
-        let first = line.find(|c: char| c.is_numeric()).unwrap();
-        let first = line.chars().nth(first).unwrap().to_digit(10).unwrap();
+    for (i, line) in lines.into_iter().enumerate() {
+        let mut digits = line.chars().filter_map(|c| c.to_digit(10));
 
-        let last = line.rfind(|c: char| c.is_numeric()).unwrap();
-        let last = line.chars().nth(last).unwrap().to_digit(10).unwrap();
+        let first = digits.next().ok_or_else(|| no_digit(i))?;
+        let last = digits.next_back().unwrap_or(first);
 
         total += (first * 10) + last;
     }
 
-    total as i64
+    Ok(Answer::from(total as i64))
 }
 
-pub fn part_two(input: &str) -> i64 {
+fn solve_two(lines: Vec<&str>) -> Result<Answer> {
     let numbers = HashMap::from([
         ("one", 1),
         ("two", 2),
@@ -42,34 +106,32 @@ pub fn part_two(input: &str) -> i64 {
 
     let mut total = 0;
 
-    for line in input.lines() {
-        let first = find_earliest_val(line, &numbers);
-        let last = find_latest_val(line, &numbers);
+    for (i, line) in lines.into_iter().enumerate() {
+        let first = find_earliest_val(line, &numbers).ok_or_else(|| no_digit(i))?;
+        let last = find_latest_val(line, &numbers).ok_or_else(|| no_digit(i))?;
 
         total += (first * 10) + last;
     }
 
-    total
+    Ok(Answer::from(total))
 }
 
-fn find_earliest_val(line: &str, numbers: &HashMap<&str, i64>) -> i64 {
+fn find_earliest_val(line: &str, numbers: &HashMap<&str, i64>) -> Option<i64> {
     numbers
         .iter()
         .map(|(&k, &v)| (line.find(k), v))
         .filter_map(|(i, v)| i.map(|ind| (ind, v)))
         .min_by(|(a, _), (b, _)| a.cmp(b))
-        .expect("No numbers found")
-        .1
+        .map(|(_, v)| v)
 }
 
-fn find_latest_val(line: &str, numbers: &HashMap<&str, i64>) -> i64 {
+fn find_latest_val(line: &str, numbers: &HashMap<&str, i64>) -> Option<i64> {
     numbers
         .iter()
         .map(|(&k, &v)| (line.rfind(k), v))
         .filter_map(|(i, v)| i.map(|ind| (ind, v)))
         .max_by(|(a, _), (b, _)| a.cmp(b))
-        .unwrap()
-        .1
+        .map(|(_, v)| v)
 }
 
 #[cfg(test)]
@@ -78,28 +140,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = r#"
-1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet
-"#
-        .trim();
-        assert_eq!(part_one(input), 142);
+        assert_eq!(part_one(EXAMPLES[0].input).unwrap(), 142);
+    }
+
+    #[test]
+    fn test_line_without_digit() {
+        assert_eq!(
+            part_one("1abc2\npqrstu").unwrap_err(),
+            Error::missing("line 2 has no digit")
+        );
     }
 
     #[test]
     fn test_part_two() {
-        let input = r#"
-        two1nine
-        eightwothree
-        abcone2threexyz
-        xtwone3four
-        4nineeightseven2
-        zoneight234
-        7pqrstsixteen
-                "#
-        .trim();
-        assert_eq!(part_two(input), 281);
+        assert_eq!(part_two(EXAMPLES[1].input).unwrap(), 281);
     }
 }
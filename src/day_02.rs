@@ -1,34 +1,87 @@
 use lalrpop_util::lalrpop_mod;
 
 pub(crate) mod ast;
-lalrpop_mod!(pub(crate) parser, "/day_02/parser.rs");
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    pub(crate) parser,
+    "/day_02/parser.rs"
+);
 
-use ast::{Cube, Round};
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+use ast::{Cube, Game, Round};
 use parser::GamesParser;
 
-pub fn part_one(input: &str) -> u32 {
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 2, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#,
+    answers: &[(1, StaticAnswer::I64(8)), (2, StaticAnswer::I64(2286))],
+}];
+
+fn parse(input: &str) -> Result<Vec<Game>> {
+    GamesParser::new()
+        .parse(input)
+        .map_err(|e| Error::from_parser(input, e))
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+fn solve_one(games: Vec<Game>) -> Result<Answer> {
     let maxima = Round::from_cubes(vec![
         Cube(12, "red".to_string()),
         Cube(13, "green".to_string()),
         Cube(14, "blue".to_string()),
     ]);
 
-    GamesParser::new()
-        .parse(input)
-        .unwrap()
+    Ok(games
         .into_iter()
         .filter(|game| game.is_possible_given(&maxima))
         .map(|game| game.id)
-        .sum()
+        .sum::<u32>()
+        .into())
 }
 
-pub fn part_two(input: &str) -> u32 {
-    GamesParser::new()
-        .parse(input)
-        .unwrap()
+fn solve_two(games: Vec<Game>) -> Result<Answer> {
+    Ok(games
         .into_iter()
         .map(|game| game.power())
-        .sum()
+        .sum::<u32>()
+        .into())
 }
 
 #[cfg(test)]
@@ -36,22 +89,29 @@ mod tests {
     use super::*;
 
     fn input() -> &'static str {
-        r#"
-        Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#
-            .trim()
+        EXAMPLES[0].input
     }
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(input()), 8);
+        assert_eq!(part_one(input()).unwrap(), 8);
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(input()), 2286);
+        assert_eq!(part_two(input()).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_malformed_game() {
+        let error = part_one("Game 1: 3 blue\nGame 2; 4 red").unwrap_err();
+
+        assert_eq!(
+            error.render(),
+            r#"parse error at line 2, column 7: unexpected `;`, expected `:`
+  |
+2 | Game 2; 4 red
+  |       ^"#
+        );
     }
 }
@@ -17,7 +17,7 @@ impl Round {
     pub fn is_possible_given(&self, maxima: &Round) -> bool {
         self.cubes
             .iter()
-            .all(|(color, &count)| maxima.cubes[color] >= count)
+            .all(|(color, &count)| maxima.cubes.get(color).is_some_and(|&max| max >= count))
     }
 }
 
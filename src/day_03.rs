@@ -0,0 +1,217 @@
+use std::ops::Range;
+
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+
+/// A number in the schematic, with the row it is on and the columns its
+/// digits cover. Columns are byte offsets into the row.
+#[derive(Debug, PartialEq)]
+struct Number {
+    value: i64,
+    row: usize,
+    columns: Range<usize>,
+}
+
+/// Anything in the schematic that is neither a digit nor a `.`.
+#[derive(Debug, PartialEq)]
+struct Symbol {
+    symbol: char,
+    row: usize,
+    column: usize,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+}
+
+impl Number {
+    /// Whether the symbol touches one of the number's digits, diagonally
+    /// included.
+    fn is_adjacent(&self, symbol: &Symbol) -> bool {
+        symbol.row + 1 >= self.row
+            && symbol.row <= self.row + 1
+            && symbol.column + 1 >= self.columns.start
+            && symbol.column <= self.columns.end
+    }
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 3, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."#,
+    answers: &[(1, StaticAnswer::I64(4361)), (2, StaticAnswer::I64(467835))],
+}];
+
+fn parse(input: &str) -> Result<Schematic> {
+    let mut schematic = Schematic::default();
+
+    for (row, line) in input.lines().enumerate() {
+        let mut column = 0;
+
+        while let Some(c) = line[column..].chars().next() {
+            if c.is_ascii_digit() {
+                let rest = &line[column..];
+                let digits = &rest[..rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len())];
+                let value = digits.parse().map_err(|_| {
+                    Error::at_token(input, digits, format!("invalid number `{}`", digits))
+                })?;
+
+                schematic.numbers.push(Number {
+                    value,
+                    row,
+                    columns: column..column + digits.len(),
+                });
+                column += digits.len();
+            } else {
+                if c != '.' && !c.is_whitespace() {
+                    schematic.symbols.push(Symbol {
+                        symbol: c,
+                        row,
+                        column,
+                    });
+                }
+                column += c.len_utf8();
+            }
+        }
+    }
+
+    Ok(schematic)
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+/// Sums the part numbers, which are the numbers next to any symbol.
+fn solve_one(schematic: Schematic) -> Result<Answer> {
+    Ok(schematic
+        .numbers
+        .iter()
+        .filter(|number| {
+            schematic
+                .symbols
+                .iter()
+                .any(|symbol| number.is_adjacent(symbol))
+        })
+        .map(|number| number.value)
+        .sum::<i64>()
+        .into())
+}
+
+/// Sums the gear ratios. A gear is a `*` next to exactly two numbers, and
+/// its ratio is their product.
+fn solve_two(schematic: Schematic) -> Result<Answer> {
+    Ok(schematic
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.symbol == '*')
+        .filter_map(|symbol| {
+            let numbers = schematic
+                .numbers
+                .iter()
+                .filter(|number| number.is_adjacent(symbol))
+                .collect::<Vec<_>>();
+
+            match numbers[..] {
+                [first, second] => Some(first.value * second.value),
+                _ => None,
+            }
+        })
+        .sum::<i64>()
+        .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> &'static str {
+        EXAMPLES[0].input
+    }
+
+    #[test]
+    fn test_parse() {
+        let schematic = parse("467..114..\n...*......").unwrap();
+
+        assert_eq!(
+            schematic,
+            Schematic {
+                numbers: vec![
+                    Number {
+                        value: 467,
+                        row: 0,
+                        columns: 0..3,
+                    },
+                    Number {
+                        value: 114,
+                        row: 0,
+                        columns: 5..8,
+                    },
+                ],
+                symbols: vec![Symbol {
+                    symbol: '*',
+                    row: 1,
+                    column: 3,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one(input()).unwrap(), 4361);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(input()).unwrap(), 467835);
+    }
+
+    #[test]
+    fn test_edges() {
+        // Numbers at the ends of rows still touch symbols diagonally, but not
+        // ones two columns away.
+        assert_eq!(part_one("12.\n..#\n#..\n..34").unwrap(), 12);
+    }
+}
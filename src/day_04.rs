@@ -3,25 +3,102 @@ use std::collections::HashSet;
 use lalrpop_util::lalrpop_mod;
 
 pub(crate) mod ast;
-lalrpop_mod!(pub(crate) parser, "/day_04/parser.rs");
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    pub(crate) parser,
+    "/day_04/parser.rs"
+);
 
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+use ast::Card;
 use parser::CardsParser;
 
-pub fn part_one(input: &str) -> i64 {
+pub struct Day04;
+
+impl Solver for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 4, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#,
+    answers: &[(1, StaticAnswer::I64(13)), (2, StaticAnswer::I64(30))],
+}];
+
+fn parse(input: &str) -> Result<Vec<Card>> {
     CardsParser::new()
         .parse(input)
-        .unwrap()
-        .into_iter()
-        .map(|card| {
-            card.1
-                .into_iter()
-                .collect::<HashSet<_>>()
-                .intersection(&card.2.into_iter().collect::<HashSet<_>>())
-                .count()
-        })
+        .map_err(|e| Error::from_parser(input, e))
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+/// How many of the card's numbers are winning numbers.
+fn matches(card: &Card) -> usize {
+    let winning = card.0.iter().collect::<HashSet<_>>();
+
+    card.1
+        .iter()
+        .filter(|number| winning.contains(number))
+        .count()
+}
+
+fn solve_one(cards: Vec<Card>) -> Result<Answer> {
+    Ok(cards
+        .iter()
+        .map(matches)
         .filter(|&count| count > 0)
         .map(|count| 2i64.pow((count - 1) as u32))
-        .sum()
+        .sum::<i64>()
+        .into())
+}
+
+/// Each card wins one copy of each of the next `n` cards, where `n` is its
+/// number of matches, and every copy wins in turn. Copies never go past the
+/// last card.
+fn solve_two(cards: Vec<Card>) -> Result<Answer> {
+    let mut copies = vec![1i64; cards.len()];
+
+    for (i, card) in cards.iter().enumerate() {
+        let end = (i + 1 + matches(card)).min(cards.len());
+
+        for j in i + 1..end {
+            copies[j] += copies[i];
+        }
+    }
+
+    Ok(copies.iter().sum::<i64>().into())
 }
 
 #[cfg(test)]
@@ -29,17 +106,29 @@ mod tests {
     use super::*;
 
     fn input() -> &'static str {
-        r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#
-            .trim()
+        EXAMPLES[0].input
     }
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(input()), 13);
+        assert_eq!(part_one(input()).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(input()).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_malformed_card() {
+        let error = part_one("Card 1: 41 48 | 83 86\nCard 2: 13 32 61 30").unwrap_err();
+
+        assert_eq!(
+            error.render(),
+            r#"parse error at line 2, column 20: unexpected end of input, expected one of `|` or text matching `[0-9]+`
+  |
+2 | Card 2: 13 32 61 30
+  |                    ^"#
+        );
     }
 }
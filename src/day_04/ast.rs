@@ -1 +1,2 @@
-pub struct Card(pub i64, pub Vec<i64>, pub Vec<i64>);
+/// A scratchcard's winning numbers and the numbers you have.
+pub struct Card(pub Vec<i64>, pub Vec<i64>);
@@ -1,23 +1,106 @@
 use lalrpop_util::lalrpop_mod;
 
 pub(crate) mod ast;
-lalrpop_mod!(pub(crate) parser, "/day_05/parser.rs");
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    pub(crate) parser,
+    "/day_05/parser.rs"
+);
 
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+use ast::{Puzzle, Puzzle2};
 use parser::{Puzzle2Parser, PuzzleParser};
 
-pub fn part_one(input: &str) -> i64 {
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse_one, solve_one),
+            2 => Solution::measure(input, parse_two, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 5, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#,
+    answers: &[(1, StaticAnswer::I64(35)), (2, StaticAnswer::I64(46))],
+}];
+
+fn parse_one(input: &str) -> Result<Puzzle> {
     PuzzleParser::new()
         .parse(input)
-        .unwrap()
-        .find_closest_location()
+        .map_err(|e| Error::from_parser(input, e))
+}
+
+fn parse_two(input: &str) -> Result<Puzzle2> {
+    Puzzle2Parser::new()
+        .parse(input)
+        .map_err(|e| Error::from_parser(input, e))
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse_one(input)?)
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let puzzle = Puzzle2Parser::new().parse(input).unwrap();
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse_two(input)?)
+}
 
-    println!("{:?}", puzzle);
+fn solve_one(puzzle: Puzzle) -> Result<Answer> {
+    puzzle.find_closest_location().map(Answer::from)
+}
 
-    puzzle.find_closest_location()
+fn solve_two(puzzle: Puzzle2) -> Result<Answer> {
+    puzzle.find_closest_location().map(Answer::from)
 }
 
 #[cfg(test)]
@@ -25,49 +108,26 @@ mod tests {
     use super::*;
 
     fn input() -> &'static str {
-        r#"seeds: 79 14 55 13
-
-        seed-to-soil map:
-        50 98 2
-        52 50 48
-        
-        soil-to-fertilizer map:
-        0 15 37
-        37 52 2
-        39 0 15
-        
-        fertilizer-to-water map:
-        49 53 8
-        0 11 42
-        42 0 7
-        57 7 4
-        
-        water-to-light map:
-        88 18 7
-        18 25 70
-        
-        light-to-temperature map:
-        45 77 23
-        81 45 19
-        68 64 13
-        
-        temperature-to-humidity map:
-        0 69 1
-        1 0 69
-        
-        humidity-to-location map:
-        60 56 37
-        56 93 4"#
-            .trim()
+        EXAMPLES[0].input
     }
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(input()), 35);
+        assert_eq!(part_one(input()).unwrap(), 35);
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(input()), 46);
+        assert_eq!(part_two(input()).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_missing_map() {
+        let input = input().replace("temperature-to-humidity", "heat-to-humidity");
+
+        assert_eq!(
+            part_one(&input).unwrap_err(),
+            Error::missing("no map from temperature")
+        );
     }
 }
@@ -1,5 +1,12 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, Result};
+use crate::progress;
+
+/// How many seeds to map between progress reports. A power of two, so the
+/// hot loop can check it with a mask.
+const PROGRESS_INTERVAL: u64 = 1 << 20;
+
 #[derive(Clone, Debug)]
 pub struct Mapping(i64, i64, i64);
 
@@ -31,9 +38,7 @@ impl Map {
 #[derive(Debug)]
 pub struct Puzzle {
     pub seeds: Vec<i64>,
-    pub maps: Vec<Map>,
     pub source_maps: HashMap<String, Map>,
-    pub target_maps: HashMap<String, Map>,
 }
 
 #[derive(Debug)]
@@ -42,85 +47,97 @@ pub struct SeedPair(pub i64, pub i64);
 #[derive(Debug)]
 pub struct Puzzle2 {
     pub seeds: Vec<SeedPair>,
-    pub maps: Vec<Map>,
     pub source_maps: HashMap<String, Map>,
-    pub target_maps: HashMap<String, Map>,
 }
 
 impl Puzzle {
     pub fn new(seeds: Vec<i64>, maps: Vec<Map>) -> Self {
-        let mut source_maps = HashMap::new();
-        let mut target_maps = HashMap::new();
-
-        for map in maps.iter() {
-            source_maps.insert(map.source.clone(), map.clone());
-            target_maps.insert(map.target.clone(), map.clone());
-        }
-
         Self {
             seeds,
-            maps,
-            source_maps,
-            target_maps,
+            source_maps: by_source(maps),
         }
     }
 
-    pub fn map(&self, input: i64) -> i64 {
-        let mut label = "seed".to_string();
-        let mut output = input;
-
-        while label != "location" {
-            let map = self.source_maps.get(&label).unwrap();
-            label = map.target.clone();
-
-            output = map.map(output);
-        }
-
-        output
+    pub fn map(&self, input: i64) -> Result<i64> {
+        map_to_location(&self.source_maps, input)
     }
 
-    pub fn find_closest_location(&self) -> i64 {
-        self.seeds.iter().map(|&seed| self.map(seed)).min().unwrap()
+    pub fn find_closest_location(&self) -> Result<i64> {
+        self.seeds
+            .iter()
+            .map(|&seed| self.map(seed))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .min()
+            .ok_or_else(|| Error::missing("no seeds"))
     }
 }
 
 impl Puzzle2 {
     pub fn new(seeds: Vec<SeedPair>, maps: Vec<Map>) -> Self {
-        let mut source_maps = HashMap::new();
-        let mut target_maps = HashMap::new();
-
-        for map in maps.iter() {
-            source_maps.insert(map.source.clone(), map.clone());
-            target_maps.insert(map.target.clone(), map.clone());
-        }
-
         Self {
             seeds,
-            maps,
-            source_maps,
-            target_maps,
+            source_maps: by_source(maps),
         }
     }
 
-    pub fn map(&self, input: i64) -> i64 {
-        let mut label = "seed".to_string();
-        let mut output = input;
+    pub fn map(&self, input: i64) -> Result<i64> {
+        map_to_location(&self.source_maps, input)
+    }
+
+    pub fn find_closest_location(&self) -> Result<i64> {
+        let total = self
+            .seeds
+            .iter()
+            .map(|SeedPair(_, length)| (*length).max(0) as u64)
+            .sum();
+        let mut done = 0;
+        let mut closest = None;
 
-        while label != "location" {
-            let map = self.source_maps.get(&label).unwrap();
-            label = map.target.clone();
+        for SeedPair(start, length) in &self.seeds {
+            for seed in *start..(*start + *length) {
+                let location = self.map(seed)?;
+                closest = Some(closest.map_or(location, |c: i64| c.min(location)));
 
-            output = map.map(output);
+                done += 1;
+
+                if done & (PROGRESS_INTERVAL - 1) == 0 {
+                    progress::report(done, total)?;
+                }
+            }
         }
 
-        output
+        progress::report(done, total)?;
+
+        closest.ok_or_else(|| Error::missing("no seeds"))
     }
+}
 
-    pub fn find_closest_location(&self) -> i64 {
-        self.seeds
-            .iter()
-            .map(|SeedPair(a, b)| (*a..(*a + *b)).map(|x| self.map(x)).min().unwrap())
-            .min()
-            .unwrap()
+/// Indexes the maps by the category they map from.
+fn by_source(maps: Vec<Map>) -> HashMap<String, Map> {
+    maps.into_iter()
+        .map(|map| (map.source.clone(), map))
+        .collect()
+}
+
+/// Follows the maps from `seed` until reaching `location`, failing if a map
+/// is missing or the maps loop back on themselves.
+fn map_to_location(source_maps: &HashMap<String, Map>, input: i64) -> Result<i64> {
+    let mut label = "seed";
+    let mut output = input;
+
+    for _ in 0..=source_maps.len() {
+        if label == "location" {
+            return Ok(output);
+        }
+
+        let map = source_maps
+            .get(label)
+            .ok_or_else(|| Error::missing(format!("no map from {}", label)))?;
+        label = &map.target;
+
+        output = map.map(output);
     }
+
+    Err(Error::missing("no chain of maps from seed to location"))
 }
@@ -1,64 +1,104 @@
-pub fn part_one(input: &str) -> i64 {
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse_one, solve_one),
+            2 => Solution::measure(input, parse_two, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 6, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"Time:      7  15   30
+Distance:  9  40  200"#,
+    answers: &[(1, StaticAnswer::I64(288)), (2, StaticAnswer::I64(71503))],
+}];
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse_one(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse_two(input)?)
+}
+
+/// Parses the races as a list of `(time, distance)` pairs.
+fn parse_one(input: &str) -> Result<Vec<(i64, i64)>> {
     let mut lines = input.lines();
 
-    let time = lines
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .trim()
+    let time = field(lines.next(), "time")?
         .split_ascii_whitespace()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<_>>();
-
-    let distance = lines
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .trim()
+        .map(|x| number(input, x))
+        .collect::<Result<Vec<_>>>()?;
+
+    let distance = field(lines.next(), "distance")?
         .split_ascii_whitespace()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<_>>();
+        .map(|x| number(input, x))
+        .collect::<Result<Vec<_>>>()?;
 
-    time.iter()
-        .zip(distance.iter())
-        .map(|(t, d)| ways_to_win_single(*t, *d))
-        .product()
+    Ok(time.into_iter().zip(distance).collect())
 }
 
-pub fn part_two(input: &str) -> i64 {
+/// Parses a single `(time, distance)` race, ignoring the spaces between digits.
+fn parse_two(input: &str) -> Result<(i64, i64)> {
     let mut lines = input.lines();
 
-    let time = lines
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .trim()
-        .split_ascii_whitespace()
-        .collect::<Vec<_>>()
-        .join("")
-        .parse::<i64>()
-        .unwrap();
-
-    let distance = lines
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .trim()
+    let time = field(lines.next(), "time")?;
+    let distance = field(lines.next(), "distance")?;
+
+    Ok((kerned_number(input, time)?, kerned_number(input, distance)?))
+}
+
+/// The values after the colon on one of the input lines.
+fn field<'a>(line: Option<&'a str>, name: &str) -> Result<&'a str> {
+    line.and_then(|line| line.split_once(':'))
+        .map(|(_, values)| values.trim())
+        .ok_or_else(|| Error::missing(format!("no {} line", name)))
+}
+
+fn number(input: &str, token: &str) -> Result<i64> {
+    token
+        .parse()
+        .map_err(|_| Error::at_token(input, token, format!("invalid number `{}`", token)))
+}
+
+fn kerned_number(input: &str, values: &str) -> Result<i64> {
+    values
         .split_ascii_whitespace()
-        .collect::<Vec<_>>()
-        .join("")
-        .parse::<i64>()
-        .unwrap();
+        .collect::<String>()
+        .parse()
+        .map_err(|_| Error::at_token(input, values, format!("invalid number `{}`", values)))
+}
+
+fn solve_one(races: Vec<(i64, i64)>) -> Result<Answer> {
+    Ok(races
+        .into_iter()
+        .map(|(t, d)| ways_to_win_single(t, d))
+        .product::<i64>()
+        .into())
+}
 
-    ways_to_win_single(time, distance)
+fn solve_two((time, distance): (i64, i64)) -> Result<Answer> {
+    Ok(ways_to_win_single(time, distance).into())
 }
 
 /// Let `a` be the duration of a race. Let `k` be the max distance.
@@ -80,18 +120,28 @@ mod tests {
     use super::*;
 
     fn input() -> &'static str {
-        r#"Time:      7  15   30
-        Distance:  9  40  200"#
-            .trim()
+        EXAMPLES[0].input
     }
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(input()), 288);
+        assert_eq!(part_one(input()).unwrap(), 288);
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(input()), 71503);
+        assert_eq!(part_two(input()).unwrap(), 71503);
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        assert_eq!(
+            part_one("Time: 7 1x5\nDistance: 9 40").unwrap_err(),
+            Error::at_span("Time: 7 1x5", 8..11, "invalid number `1x5`")
+        );
+        assert_eq!(
+            part_two("Time: 7 15").unwrap_err(),
+            Error::missing("no distance line")
+        );
     }
 }
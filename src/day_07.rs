@@ -0,0 +1,168 @@
+use lalrpop_util::lalrpop_mod;
+
+pub(crate) mod ast;
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    pub(crate) parser,
+    "/day_07/parser.rs"
+);
+
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+use ast::{Jokers, Play, Rule, Standard};
+use parser::PlaysParser;
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 7, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483"#,
+    answers: &[(1, StaticAnswer::I64(6440)), (2, StaticAnswer::I64(5905))],
+}];
+
+fn parse(input: &str) -> Result<Vec<Play>> {
+    PlaysParser::new()
+        .parse(input)
+        .map_err(|e| Error::from_parser(input, e))
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+fn solve_one(plays: Vec<Play>) -> Result<Answer> {
+    Ok(winnings(plays, &Standard).into())
+}
+
+fn solve_two(plays: Vec<Play>) -> Result<Answer> {
+    Ok(winnings(plays, &Jokers).into())
+}
+
+/// Ranks the hands under the rule, weakest first, and sums each bid times its
+/// hand's rank.
+fn winnings(mut plays: Vec<Play>, rule: &impl Rule) -> i64 {
+    plays.sort_by_cached_key(|play| rule.rank(&play.hand));
+
+    plays
+        .iter()
+        .zip(1..)
+        .map(|(play, rank)| play.bid * rank)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ast::{Hand, HandType};
+
+    fn input() -> &'static str {
+        EXAMPLES[0].input
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one(input()).unwrap(), 6440);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(input()).unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_hand_types() {
+        let types = ["32T3K", "KK677", "T55J5", "QQQJA", "KKKK2", "JJJJJ"].map(|cards| {
+            (
+                Standard.hand_type(&Hand::new(cards)),
+                Jokers.hand_type(&Hand::new(cards)),
+            )
+        });
+
+        assert_eq!(
+            types,
+            [
+                (HandType::OnePair, HandType::OnePair),
+                (HandType::TwoPair, HandType::TwoPair),
+                (HandType::ThreeOfAKind, HandType::FourOfAKind),
+                (HandType::ThreeOfAKind, HandType::FourOfAKind),
+                (HandType::FourOfAKind, HandType::FourOfAKind),
+                (HandType::FiveOfAKind, HandType::FiveOfAKind),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jokers_rank_types_up_and_ties_down() {
+        let (jokers, queens) = (Hand::new("JKKK2"), Hand::new("QQQQ2"));
+
+        assert!(Standard.rank(&jokers) < Standard.rank(&queens));
+        assert!(Jokers.rank(&jokers) < Jokers.rank(&queens));
+        assert!(Jokers.rank(&Hand::new("JJJJ2")) > Jokers.rank(&Hand::new("AAAKK")));
+    }
+
+    #[test]
+    fn test_digit_hand() {
+        assert_eq!(
+            parse("23456 10").unwrap(),
+            [Play {
+                hand: Hand([2, 3, 4, 5, 6]),
+                bid: 10,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_digit_bid() {
+        assert_eq!(
+            parse("23456 23456\nKK677 28").unwrap(),
+            [
+                Play {
+                    hand: Hand([2, 3, 4, 5, 6]),
+                    bid: 23456,
+                },
+                Play {
+                    hand: Hand([13, 13, 6, 7, 7]),
+                    bid: 28,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_hand() {
+        let error = part_one("32T3K 765\nT55J 684").unwrap_err();
+
+        assert!(error.render().starts_with("parse error at line 2"));
+    }
+}
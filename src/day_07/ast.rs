@@ -0,0 +1,115 @@
+/// A card's value, from 2 up to 14 for an ace.
+pub type Card = u8;
+
+pub const JACK: Card = 11;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hand(pub [Card; 5]);
+
+impl Hand {
+    /// Reads a hand like `T55J5`. The grammar only lets valid cards through.
+    pub fn new(cards: &str) -> Self {
+        let mut hand = [0; 5];
+
+        for (card, c) in hand.iter_mut().zip(cards.bytes()) {
+            *card = match c {
+                b'T' => 10,
+                b'J' => JACK,
+                b'Q' => 12,
+                b'K' => 13,
+                b'A' => 14,
+                _ => c - b'0',
+            };
+        }
+
+        Self(hand)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Play {
+    pub hand: Hand,
+    pub bid: i64,
+}
+
+/// The type of a hand, from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// Classifies cards by how many there are of each, with `wild` more cards
+    /// that join whichever group is largest.
+    pub fn classify(cards: impl IntoIterator<Item = Card>, wild: usize) -> Self {
+        let mut counts = [0; 15];
+
+        for card in cards {
+            counts[card as usize] += 1;
+        }
+
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        match (counts[0] + wild, counts[1]) {
+            (5, _) => Self::FiveOfAKind,
+            (4, _) => Self::FourOfAKind,
+            (3, 2) => Self::FullHouse,
+            (3, _) => Self::ThreeOfAKind,
+            (2, 2) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
+        }
+    }
+}
+
+/// Decides how hands rank: first by the type a hand counts as, then card by
+/// card on what each card is worth.
+pub trait Rule {
+    fn hand_type(&self, hand: &Hand) -> HandType;
+
+    fn strength(&self, card: Card) -> u8;
+
+    /// What hands are sorted by, weakest first.
+    fn rank(&self, hand: &Hand) -> (HandType, [u8; 5]) {
+        (self.hand_type(hand), hand.0.map(|card| self.strength(card)))
+    }
+}
+
+/// Every card counts as what it is.
+pub struct Standard;
+
+impl Rule for Standard {
+    fn hand_type(&self, hand: &Hand) -> HandType {
+        HandType::classify(hand.0, 0)
+    }
+
+    fn strength(&self, card: Card) -> u8 {
+        card
+    }
+}
+
+/// Jacks are jokers, which stand in for whatever card makes the hand's type
+/// strongest but are the weakest card when breaking ties.
+pub struct Jokers;
+
+impl Rule for Jokers {
+    fn hand_type(&self, hand: &Hand) -> HandType {
+        let jokers = hand.0.iter().filter(|&&card| card == JACK).count();
+
+        HandType::classify(hand.0.into_iter().filter(|&card| card != JACK), jokers)
+    }
+
+    fn strength(&self, card: Card) -> u8 {
+        if card == JACK {
+            1
+        } else {
+            card
+        }
+    }
+}
@@ -1,24 +1,84 @@
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
 use std::collections::HashMap;
 
 type Paths<'a> = HashMap<&'a str, (&'a str, &'a str)>;
 
-pub fn part_one(input: &str) -> i64 {
-    let (sequence, paths) = construct_from_input(input);
+pub struct Day08;
 
-    find_end("AAA", |node| node == "ZZZ", sequence, &paths)
+impl Solver for Day08 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, construct_from_input, solve_one),
+            2 => Solution::measure(input, construct_from_input, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 8, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        name: "one start",
+        input: r#"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)"#,
+        answers: &[(1, StaticAnswer::I64(6))],
+    },
+    Example {
+        name: "many starts",
+        input: r#"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"#,
+        answers: &[(2, StaticAnswer::I64(6))],
+    },
+];
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(construct_from_input(input)?)
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let (sequence, paths) = construct_from_input(input);
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(construct_from_input(input)?)
+}
+
+fn solve_one((sequence, paths): (&str, Paths<'_>)) -> Result<Answer> {
+    find_end("AAA", |node| node == "ZZZ", sequence, &paths).map(Answer::from)
+}
 
+fn solve_two((sequence, paths): (&str, Paths<'_>)) -> Result<Answer> {
     let find_end = |x| find_end(x, |node| node.ends_with('Z'), sequence, &paths);
 
     paths
         .iter()
         .filter(|(&key, _)| key.ends_with('A'))
         .map(|(&key, _)| find_end(key))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
         .reduce(|a, b| (a * b) / gcd(a, b))
-        .unwrap()
+        .map(Answer::from)
+        .ok_or_else(|| Error::missing("no node ends with A"))
 }
 
 fn gcd(a: i64, b: i64) -> i64 {
@@ -34,14 +94,16 @@ fn find_end(
     condition: impl Fn(&str) -> bool,
     sequence: &str,
     paths: &Paths<'_>,
-) -> i64 {
+) -> Result<i64> {
     let mut current = start;
 
     let mut count = 0;
 
     while !condition(current) {
         for key in sequence.chars() {
-            let (left, right) = paths.get(current).unwrap();
+            let (left, right) = paths
+                .get(current)
+                .ok_or_else(|| Error::missing(format!("no node {}", current)))?;
 
             if key == 'L' {
                 current = left;
@@ -57,18 +119,31 @@ fn find_end(
         }
     }
 
-    count
+    Ok(count)
 }
 
-fn construct_from_input(input: &str) -> (&str, Paths<'_>) {
+fn construct_from_input(input: &str) -> Result<(&str, Paths<'_>)> {
     let mut lines = input.lines();
 
-    let sequence = lines.next().unwrap();
+    let sequence = lines
+        .next()
+        .filter(|sequence| !sequence.is_empty())
+        .ok_or_else(|| Error::missing("no instructions"))?;
+
+    if let Some(i) = sequence.find(|c| c != 'L' && c != 'R') {
+        return Err(Error::at_token(
+            input,
+            &sequence[i..],
+            "expected only `L` and `R` instructions",
+        ));
+    }
 
     let mut paths = HashMap::new();
 
     for line in lines.filter(|&x| !x.is_empty()) {
-        let (from, to) = line.split_once(" = ").unwrap();
+        let invalid = || Error::at_token(input, line, "expected `NODE = (LEFT, RIGHT)`");
+
+        let (from, to) = line.split_once(" = ").ok_or_else(invalid)?;
 
         let from = from.trim();
         let to = to.trim();
@@ -77,12 +152,12 @@ fn construct_from_input(input: &str) -> (&str, Paths<'_>) {
             .trim_start_matches('(')
             .trim_end_matches(')')
             .split_once(", ")
-            .unwrap();
+            .ok_or_else(invalid)?;
 
         paths.insert(from, (left, right));
     }
 
-    (sequence, paths)
+    Ok((sequence, paths))
 }
 
 #[cfg(test)]
@@ -91,31 +166,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = r#"
-        LLR
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)"#
-            .trim();
-
-        assert_eq!(part_one(input), 6);
+        assert_eq!(part_one(EXAMPLES[0].input).unwrap(), 6);
     }
 
     #[test]
     fn test_part_two() {
-        let input = r#"
-        LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)"#
-            .trim();
+        assert_eq!(part_two(EXAMPLES[1].input).unwrap(), 6);
+    }
 
-        assert_eq!(part_two(input), 6);
+    #[test]
+    fn test_missing_node() {
+        assert_eq!(
+            part_one("L\n\nAAA = (BBB, BBB)").unwrap_err(),
+            Error::missing("no node BBB")
+        );
     }
 }
@@ -1,64 +1,113 @@
 pub(crate) mod models;
 
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
 use models::*;
 
-pub fn part_one(input: &str) -> i64 {
+pub struct Day09;
+
+impl Solver for Day09 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 9, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45"#,
+    answers: &[(1, StaticAnswer::I64(114)), (2, StaticAnswer::I64(2))],
+}];
+
+fn parse(input: &str) -> Result<Vec<Vec<i128>>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .map(|x| {
+                    x.parse::<_>()
+                        .map_err(|_| Error::at_token(input, x, format!("invalid number `{}`", x)))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+fn solve_one(histories: Vec<Vec<i128>>) -> Result<Answer> {
     let mut total = 0;
 
     let mut interpolator = Interpolator::new();
 
-    for line in input.lines() {
-        let numbers = line
-            .split_ascii_whitespace()
-            .map(|x| x.parse::<_>().unwrap())
-            .collect::<Vec<_>>();
-
+    for numbers in histories {
         let x = numbers.len() as i64;
 
-        total += interpolator.interpolate(&numbers, x).unwrap();
+        total += interpolator.interpolate(&numbers, x)?;
     }
 
-    total
+    Ok(Answer::from(total))
 }
 
-pub fn part_two(input: &str) -> i64 {
+fn solve_two(histories: Vec<Vec<i128>>) -> Result<Answer> {
     let mut total = 0;
 
     let mut interpolator = Interpolator::new();
 
-    for line in input.lines() {
-        let numbers = line
-            .split_ascii_whitespace()
-            .map(|x| x.parse::<_>().unwrap())
-            .collect::<Vec<_>>();
-
-        total += interpolator.interpolate(&numbers, -1).unwrap();
+    for numbers in histories {
+        total += interpolator.interpolate(&numbers, -1)?;
     }
 
-    total
+    Ok(Answer::from(total))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn input() -> &'static str {
+        EXAMPLES[0].input
+    }
+
     #[test]
     fn test_part_one() {
-        let input = r#"0 3 6 9 12 15
-        1 3 6 10 15 21
-        10 13 16 21 30 45"#
-            .trim();
-
-        assert_eq!(part_one(input), 114);
+        assert_eq!(part_one(input()).unwrap(), 114);
     }
 
     #[test]
     fn test_part_two() {
-        let input = r#"0 3 6 9 12 15
-        1 3 6 10 15 21
-        10 13 16 21 30 45"#
-            .trim();
+        assert_eq!(part_two(input()).unwrap(), 2);
+    }
 
-        assert_eq!(part_two(input), 2);
+    #[test]
+    fn test_invalid_number() {
+        assert_eq!(
+            part_one("0 3 6\n1 x 6").unwrap_err(),
+            Error::at_span("0 3 6\n1 x 6", 8..9, "invalid number `x`")
+        );
     }
 }
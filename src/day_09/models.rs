@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, Result};
+
 #[derive(Clone, Copy, Debug)]
 pub struct Rational(pub i128, pub i128);
 
@@ -10,9 +12,12 @@ impl Rational {
         Self(n / gcd, d / gcd)
     }
 
-    pub fn into(self) -> Result<i64, ()> {
+    pub fn into(self) -> Result<i64> {
         if self.1 != 1 {
-            return Err(());
+            return Err(Error::NonIntegral {
+                numerator: self.0,
+                denominator: self.1,
+            });
         }
 
         Ok(self.0 as i64)
@@ -142,9 +147,19 @@ impl Interpolator {
         }
     }
 
-    pub fn interpolate(&mut self, points: &[i128], x: i64) -> Result<i64, ()> {
+    pub fn interpolate(&mut self, points: &[i128], x: i64) -> Result<i64> {
+        if points.is_empty() {
+            return Err(Error::missing("no points to interpolate"));
+        }
+
         let n = points.len() as i128;
 
+        // The barycentric form divides by `x - i`, so asking for a sample
+        // point would divide by zero. It is already known, so return it.
+        if (0..n).contains(&(x as i128)) {
+            return Ok(points[x as usize] as i64);
+        }
+
         let mut denominator = Rational::from(0);
         let mut numerator = Rational::from(0);
 
@@ -190,6 +205,19 @@ mod tests {
         assert_eq!(y, polynomial(x as i128) as i64);
     }
 
+    #[test]
+    fn test_interpolate_sample_point() {
+        let mut interpolator = Interpolator::new();
+        let points = [1, 3, 6, 10, 15, 21];
+
+        for (x, &y) in points.iter().enumerate() {
+            assert_eq!(
+                interpolator.interpolate(&points, x as i64).unwrap(),
+                y as i64
+            );
+        }
+    }
+
     #[test]
     fn test_interpolate_linear() {
         let mut interpolator = Interpolator::new();
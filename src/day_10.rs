@@ -0,0 +1,309 @@
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+use crate::utils;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// The `(row, column)` one tile over in this direction.
+    fn step(self, (row, column): (i64, i64)) -> (i64, i64) {
+        match self {
+            Self::North => (row - 1, column),
+            Self::East => (row, column + 1),
+            Self::South => (row + 1, column),
+            Self::West => (row, column - 1),
+        }
+    }
+}
+
+/// The directions a pipe tile connects, or none for ground and the start.
+fn connections(tile: u8) -> &'static [Direction] {
+    match tile {
+        b'|' => &[Direction::North, Direction::South],
+        b'-' => &[Direction::East, Direction::West],
+        b'L' => &[Direction::North, Direction::East],
+        b'J' => &[Direction::North, Direction::West],
+        b'7' => &[Direction::South, Direction::West],
+        b'F' => &[Direction::South, Direction::East],
+        _ => &[],
+    }
+}
+
+#[derive(Debug)]
+struct Maze<'a> {
+    rows: Vec<&'a [u8]>,
+    start: (i64, i64),
+}
+
+impl Maze<'_> {
+    /// The tile at `(row, column)`, with ground all around the edges.
+    fn tile(&self, (row, column): (i64, i64)) -> u8 {
+        usize::try_from(row)
+            .ok()
+            .zip(usize::try_from(column).ok())
+            .and_then(|(row, column)| self.rows.get(row)?.get(column))
+            .copied()
+            .unwrap_or(b'.')
+    }
+
+    /// The two directions the start tile's hidden pipe connects. A stray
+    /// pipe can point at the start without being part of the loop, so each
+    /// neighbour that connects back is tried in turn until a walk from it
+    /// comes back round.
+    fn start_connections(&self) -> Result<[Direction; 2]> {
+        let directions = Direction::ALL
+            .into_iter()
+            .filter(|direction| {
+                connections(self.tile(direction.step(self.start))).contains(&direction.opposite())
+            })
+            .collect::<Vec<_>>();
+
+        if directions.len() < 2 {
+            return Err(Error::missing(format!(
+                "the start tile connects to {} pipes instead of 2",
+                directions.len()
+            )));
+        }
+
+        let mut broken = None;
+
+        // A loop is found from whichever end comes first, so the directions
+        // stay in order.
+        for &heading in &directions {
+            match self.walk(heading) {
+                Ok((_, back)) => return Ok([heading, back]),
+                Err(error) => {
+                    broken.get_or_insert(error);
+                }
+            }
+        }
+
+        Err(broken.expect("there were directions to walk"))
+    }
+
+    /// The tiles of the loop in order, from the start tile round to the one
+    /// before it.
+    fn find_loop(&self) -> Result<Vec<(i64, i64)>> {
+        let [heading, _] = self.start_connections()?;
+
+        Ok(self.walk(heading)?.0)
+    }
+
+    /// Follows the pipes from the start tile, setting off towards `heading`,
+    /// until they lead back to it. Returns the tiles on the way and the
+    /// direction the start tile was entered from.
+    fn walk(&self, mut heading: Direction) -> Result<(Vec<(i64, i64)>, Direction)> {
+        let mut position = self.start;
+        let mut tiles = vec![self.start];
+
+        loop {
+            position = heading.step(position);
+
+            if position == self.start {
+                return Ok((tiles, heading.opposite()));
+            }
+
+            let came_from = heading.opposite();
+            let pipe = connections(self.tile(position));
+
+            if !pipe.contains(&came_from) {
+                return Err(Error::missing(format!(
+                    "the loop breaks at line {}, column {}",
+                    position.0 + 1,
+                    position.1 + 1
+                )));
+            }
+
+            heading = pipe
+                .iter()
+                .copied()
+                .find(|&direction| direction != came_from)
+                .unwrap_or(came_from);
+            tiles.push(position);
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 10, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        name: "loop",
+        input: r#"..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ..."#,
+        answers: &[(1, StaticAnswer::I64(8)), (2, StaticAnswer::I64(1))],
+    },
+    Example {
+        name: "enclosed tiles",
+        input: r#"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."#,
+        answers: &[(1, StaticAnswer::I64(23)), (2, StaticAnswer::I64(4))],
+    },
+];
+
+fn parse(input: &str) -> Result<Maze<'_>> {
+    let mut start = None;
+
+    for (row, line) in input.lines().enumerate() {
+        for (column, c) in line.char_indices() {
+            let token = &line[column..column + c.len_utf8()];
+
+            match c {
+                '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' => {}
+                'S' if start.is_none() => start = Some((row as i64, column as i64)),
+                'S' => return Err(Error::at_token(input, token, "more than one start tile")),
+                _ => {
+                    return Err(Error::at_token(
+                        input,
+                        token,
+                        format!("unexpected tile `{}`", c),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(Maze {
+        rows: input.lines().map(str::as_bytes).collect(),
+        start: start.ok_or_else(|| Error::missing("no start tile `S`"))?,
+    })
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
+
+/// The farthest tile from the start is halfway round the loop.
+fn solve_one(maze: Maze<'_>) -> Result<Answer> {
+    Ok((maze.find_loop()?.len() as i64 / 2).into())
+}
+
+/// Counts the tiles enclosed by the loop the same way day 18 measures its
+/// lagoon, treating the centres of the loop's tiles as its vertices.
+fn solve_two(maze: Maze<'_>) -> Result<Answer> {
+    let (inside, _) = utils::lattice_points(&maze.find_loop()?);
+
+    Ok(inside.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one(EXAMPLES[0].input).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(EXAMPLES[1].input).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_start_connections() {
+        let maze = parse(EXAMPLES[0].input).unwrap();
+
+        assert_eq!(maze.start, (2, 0));
+        assert_eq!(
+            maze.start_connections().unwrap(),
+            [Direction::East, Direction::South]
+        );
+    }
+
+    #[test]
+    fn test_decoy_pipe() {
+        // The pipe above the start points at it but is not part of the loop.
+        let input = ".|..\n\
+                     .S-7\n\
+                     .|.|\n\
+                     .L-J";
+        let maze = parse(input).unwrap();
+
+        assert_eq!(
+            maze.start_connections().unwrap(),
+            [Direction::East, Direction::South]
+        );
+        assert_eq!(part_one(input).unwrap(), 4);
+        assert_eq!(part_two(input).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_squeezed_pipes() {
+        // The pipes in the middle touch, so there is no gap between tiles
+        // leading to the ground above them, but it is still outside the loop.
+        let input = "..........\n\
+                     .S------7.\n\
+                     .|F----7|.\n\
+                     .||....||.\n\
+                     .||....||.\n\
+                     .|L-7F-J|.\n\
+                     .|..||..|.\n\
+                     .L--JL--J.\n\
+                     ..........";
+
+        assert_eq!(part_two(input).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_broken_loop() {
+        assert_eq!(
+            part_one("S-7\n|.|\nL-.").unwrap_err(),
+            Error::missing("the loop breaks at line 3, column 3")
+        );
+    }
+}
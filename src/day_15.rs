@@ -1,3 +1,7 @@
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+
 fn hash(sequence: &str) -> u8 {
     let mut current = 0u8;
 
@@ -9,34 +13,123 @@ fn hash(sequence: &str) -> u8 {
     current
 }
 
+/// One step of the initialization sequence, acting on the box its label
+/// hashes to.
 enum Initialization<'a> {
+    /// Takes the lens with this label out of its box, if it is there.
     Removal(&'a str),
-    Lens(&'a str, usize, bool),
+    /// Puts a lens with this label and focal length in its box, replacing one
+    /// with the same label in place or going behind the others.
+    Lens(&'a str, usize),
 }
 
-pub fn part_one(input: &str) -> i64 {
+pub struct Day15;
+
+impl Solver for Day15 {
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse_one, solve_one),
+            2 => Solution::measure(input, parse_two, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 15, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7",
+    answers: &[(1, StaticAnswer::I64(1320)), (2, StaticAnswer::I64(145))],
+}];
+
+fn parse_one(input: &str) -> Result<Vec<&str>> {
+    Ok(input.split(',').map(|x| x.trim()).collect())
+}
+
+fn parse_two(input: &str) -> Result<Vec<Initialization<'_>>> {
     input
         .split(',')
-        .map(|x| x.trim())
+        .map(|x| {
+            let x = x.trim();
+
+            if let Some(label) = x.strip_suffix('-') {
+                Ok(Initialization::Removal(label))
+            } else {
+                let (label, length) = x
+                    .split_once('=')
+                    .ok_or_else(|| Error::at_token(input, x, "expected `-` or `=`"))?;
+                let length = length.parse::<usize>().map_err(|_| {
+                    Error::at_token(input, length, format!("invalid focal length `{}`", length))
+                })?;
+
+                Ok(Initialization::Lens(label, length))
+            }
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse_one(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse_two(input)?)
+}
+
+fn solve_one(steps: Vec<&str>) -> Result<Answer> {
+    Ok(steps
+        .into_iter()
         .map(hash)
         .fold(0i64, |acc, x| acc + (x as i64))
+        .into())
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let sequence = input.split(',').map(|x| {
-        let x = x.trim();
+fn solve_two(sequence: Vec<Initialization<'_>>) -> Result<Answer> {
+    let mut boxes: Vec<Vec<(&str, usize)>> = vec![Vec::new(); 256];
 
-        if x.ends_with('-') {
-            Initialization::Removal(&x[..x.len() - 1])
-        } else {
-            let (label, length) = x.split_once('=').unwrap();
-            let length = length.parse::<usize>().unwrap();
+    for step in sequence {
+        match step {
+            Initialization::Removal(label) => {
+                boxes[hash(label) as usize].retain(|&(other, _)| other != label);
+            }
+            Initialization::Lens(label, length) => {
+                let lenses = &mut boxes[hash(label) as usize];
 
-            Initialization::Lens(label, length, true)
+                match lenses.iter_mut().find(|(other, _)| *other == label) {
+                    Some(lens) => lens.1 = length,
+                    None => lenses.push((label, length)),
+                }
+            }
         }
-    });
+    }
+
+    Ok(focusing_power(&boxes).into())
+}
 
-    0
+/// The sum over every lens of its box number, its slot and its focal length,
+/// counting boxes and slots from one.
+fn focusing_power(boxes: &[Vec<(&str, usize)>]) -> i64 {
+    boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(slot, &(_, length))| ((i + 1) * (slot + 1) * length) as i64)
+        })
+        .sum()
 }
 
 #[cfg(test)]
@@ -50,8 +143,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        assert_eq!(part_one(EXAMPLES[0].input).unwrap(), 1320);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(EXAMPLES[0].input).unwrap(), 145);
+    }
 
-        assert_eq!(part_one(input), 1320);
+    #[test]
+    fn test_replace_keeps_slot() {
+        // `rn` and `cm` share box 0. `rn` is replaced where it is rather than
+        // moved behind `cm`, and removing `qp` from box 1 changes nothing.
+        assert_eq!(part_two("rn=1,cm=2,rn=3,qp-").unwrap(), 3 + 2 * 2);
+    }
+
+    #[test]
+    fn test_removal_closes_gap() {
+        assert_eq!(part_two("rn=1,cm=2,rn-").unwrap(), 2);
     }
 }
@@ -1,3 +1,8 @@
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
+use crate::utils;
+
 #[derive(Debug, PartialEq)]
 enum Direction {
     Right,
@@ -12,18 +17,71 @@ struct Instruction(Direction, u64);
 #[derive(Debug, PartialEq)]
 struct Line(Instruction, Instruction);
 
-pub fn part_one(input: &str) -> i64 {
-    let instructions = parse_input(input);
+pub struct Day18;
+
+impl Solver for Day18 {
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse_input, solve_one),
+            2 => Solution::measure(input, parse_input, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 18, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)"#,
+    answers: &[
+        (1, StaticAnswer::I64(62)),
+        (2, StaticAnswer::I64(952408144115)),
+    ],
+}];
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse_input(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse_input(input)?)
+}
 
+fn solve_one(instructions: Vec<Line>) -> Result<Answer> {
     let points = instructions.into_iter().map(|line| line.0).collect();
 
-    find_area(points)
+    Ok(find_area(points).into())
 }
 
-pub fn part_two(input: &str) -> i64 {
-    find_area(parse_input(input).into_iter().map(|line| line.1).collect())
+fn solve_two(instructions: Vec<Line>) -> Result<Answer> {
+    Ok(find_area(instructions.into_iter().map(|line| line.1).collect()).into())
 }
 
+/// The lagoon holds the trench around it as well as everything inside.
 fn find_area(points: Vec<Instruction>) -> i64 {
     let points = points
         .into_iter()
@@ -44,64 +102,66 @@ fn find_area(points: Vec<Instruction>) -> i64 {
             acc
         });
 
-    let mut area = 0;
-    let mut j = points.len() - 1;
-    let mut perimeter = 0.;
-
-    for i in 0..points.len() {
-        let (x1, y1) = points[i];
-        let (x2, y2) = points[j];
-
-        area += (y2 + y1) * (x2 - x1);
-
-        let segment = ((x2 - x1).abs().pow(2) as f64 + (y2 - y1).abs().pow(2) as f64).sqrt();
-        perimeter += segment;
+    let (inside, boundary) = utils::lattice_points(&points);
 
-        j = i;
-    }
-    let perimeter = perimeter as i64;
-
-    area.abs() / 2 + (perimeter / 2 + 1)
+    inside + boundary
 }
 
-fn parse_input(input: &str) -> Vec<Line> {
-    input
-        .lines()
-        .map(|line| {
-            let parts = line.split_ascii_whitespace().collect::<Vec<_>>();
-
-            let direction = match parts[0] {
-                "R" => Direction::Right,
-                "L" => Direction::Left,
-                "U" => Direction::Up,
-                "D" => Direction::Down,
-                _ => unreachable!(),
-            };
-
-            let steps = parts[1].parse::<u64>().unwrap();
-
-            let hex_code = parts[2]
-                .strip_prefix("(#")
-                .unwrap()
-                .strip_suffix(')')
-                .unwrap();
-
-            // Parse 5 hex digits into base 10 integer
-            let distance = u64::from_str_radix(&hex_code[0..5], 16).unwrap();
-            let hex_direction = match hex_code.chars().last().unwrap() {
-                '0' => Direction::Right,
-                '1' => Direction::Down,
-                '2' => Direction::Left,
-                '3' => Direction::Up,
-                _ => unreachable!(),
-            };
+fn parse_input(input: &str) -> Result<Vec<Line>> {
+    input.lines().map(|line| parse_line(input, line)).collect()
+}
 
-            Line(
-                Instruction(direction, steps),
-                Instruction(hex_direction, distance),
-            )
-        })
-        .collect::<Vec<_>>()
+fn parse_line(input: &str, line: &str) -> Result<Line> {
+    let parts = line.split_ascii_whitespace().collect::<Vec<_>>();
+
+    let [direction, steps, color] = parts[..] else {
+        return Err(Error::at_token(
+            input,
+            line,
+            "expected `DIRECTION STEPS (#COLOR)`",
+        ));
+    };
+
+    let direction = match direction {
+        "R" => Direction::Right,
+        "L" => Direction::Left,
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        _ => {
+            return Err(Error::at_token(
+                input,
+                direction,
+                format!("unknown direction `{}`", direction),
+            ))
+        }
+    };
+
+    let steps = steps
+        .parse::<u64>()
+        .map_err(|_| Error::at_token(input, steps, format!("invalid step count `{}`", steps)))?;
+
+    let invalid_color = || Error::at_token(input, color, format!("invalid color `{}`", color));
+
+    let hex_code = color
+        .strip_prefix("(#")
+        .and_then(|code| code.strip_suffix(')'))
+        .filter(|code| code.len() == 6 && code.is_ascii())
+        .ok_or_else(invalid_color)?;
+
+    // Parse 5 hex digits into base 10 integer
+    let distance = u64::from_str_radix(&hex_code[0..5], 16).map_err(|_| invalid_color())?;
+    let hex_direction = match hex_code.as_bytes()[5] {
+        b'0' => Direction::Right,
+        b'1' => Direction::Down,
+        b'2' => Direction::Left,
+        b'3' => Direction::Up,
+        _ => return Err(invalid_color()),
+    };
+
+    Ok(Line(
+        Instruction(direction, steps),
+        Instruction(hex_direction, distance),
+    ))
 }
 
 #[cfg(test)]
@@ -114,7 +174,7 @@ mod tests {
         D 5 (#0dc571)
         L 2 (#5713f0)"#;
 
-        let instructions = parse_input(input);
+        let instructions = parse_input(input).unwrap();
 
         assert_eq!(
             instructions,
@@ -135,31 +195,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parser_errors() {
+        let input = "R 6 (#70c710)\nX 5 (#0dc571)";
+
+        assert_eq!(
+            parse_input(input).unwrap_err(),
+            Error::at_span(input, 14..15, "unknown direction `X`")
+        );
+
+        let input = "R 6 (#70c71z)";
+
+        assert_eq!(
+            parse_input(input).unwrap_err(),
+            Error::at_span(input, 4..13, "invalid color `(#70c71z)`")
+        );
+    }
+
     #[test]
     fn test_part_one() {
-        let input = r#"R 6 (#70c710)
-        D 5 (#0dc571)
-        L 2 (#5713f0)
-        D 2 (#d2c081)
-        R 2 (#59c680)
-        D 2 (#411b91)
-        L 5 (#8ceee2)
-        U 2 (#caa173)
-        L 1 (#1b58a2)
-        U 2 (#caa171)
-        R 2 (#7807d2)
-        U 3 (#a77fa3)
-        L 2 (#015232)
-        U 2 (#7a21e3)"#;
-
-        assert_eq!(part_one(input), 62);
+        let input = EXAMPLES[0].input;
+
+        assert_eq!(part_one(input).unwrap(), 62);
 
         let input = r#"R 6 (#70c710)
         D 2 (#d2c081)
         L 6 (#5713f0)
         U 2 (#caa171)"#;
 
-        assert_eq!(part_one(input), 21);
+        assert_eq!(part_one(input).unwrap(), 21);
 
         let input = r#"R 6 (#70c710)
         D 5 (#0dc571)
@@ -168,7 +232,7 @@ mod tests {
         L 2 (#59c680)
         U 2 (#411b91)"#;
 
-        assert_eq!(part_one(input), 36);
+        assert_eq!(part_one(input).unwrap(), 36);
 
         let input = r#"R 6 (#70c710)
         D 4 (#0dc571)
@@ -177,7 +241,7 @@ mod tests {
         L 2 (#59c680)
         U 2 (#411b91)"#;
 
-        assert_eq!(part_one(input), 31);
+        assert_eq!(part_one(input).unwrap(), 31);
 
         let input = r#"R 6 (#70c710)
         D 5 (#0dc571)
@@ -190,26 +254,13 @@ mod tests {
         L 2 (#1b58a2)
         U 2 (#caa171)"#;
 
-        assert_eq!(part_one(input), 48);
+        assert_eq!(part_one(input).unwrap(), 48);
     }
 
     #[test]
     fn test_part_two() {
-        let input = r#"R 6 (#70c710)
-        D 5 (#0dc571)
-        L 2 (#5713f0)
-        D 2 (#d2c081)
-        R 2 (#59c680)
-        D 2 (#411b91)
-        L 5 (#8ceee2)
-        U 2 (#caa173)
-        L 1 (#1b58a2)
-        U 2 (#caa171)
-        R 2 (#7807d2)
-        U 3 (#a77fa3)
-        L 2 (#015232)
-        U 2 (#7a21e3)"#;
-
-        assert_eq!(part_two(input), 952408144115);
+        let input = EXAMPLES[0].input;
+
+        assert_eq!(part_two(input).unwrap(), 952408144115);
     }
 }
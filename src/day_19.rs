@@ -1,41 +1,88 @@
 use lalrpop_util::lalrpop_mod;
 
 pub(crate) mod ast;
-lalrpop_mod!(pub(crate) parser, "/day_19/parser.rs");
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    pub(crate) parser,
+    "/day_19/parser.rs"
+);
 
-use ast::Constraint;
+use ast::{Constraint, Engine};
 use parser::EngineParser;
 
-use tracing::info;
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::{Error, Result};
+use crate::solver::{Example, Solution, Solver};
 
-pub fn part_one(input: &str) -> i64 {
-    let engine = EngineParser::new().parse(input).unwrap();
+pub struct Day19;
 
-    let start = std::time::Instant::now();
-    let result = engine.solve() as i64;
-    let elapsed = start.elapsed();
+impl Solver for Day19 {
+    fn day(&self) -> u8 {
+        19
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    fn run(&self, part: u8, input: &str) -> Result<Solution> {
+        match part {
+            1 => Solution::measure(input, parse, solve_one),
+            2 => Solution::measure(input, parse, solve_two),
+            _ => Err(Error::UnsupportedPart { day: 19, part }),
+        }
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
 
-    info!(
-        "Traversed inner tree in {}",
-        crate::utils::format_duration(elapsed)
-    );
+const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: r#"px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
 
-    result
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}"#,
+    answers: &[
+        (1, StaticAnswer::I64(19114)),
+        (2, StaticAnswer::I64(167409079868000)),
+    ],
+}];
+
+fn parse(input: &str) -> Result<Engine> {
+    EngineParser::new()
+        .parse(input)
+        .map_err(|e| Error::from_parser(input, e))
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let engine = EngineParser::new().parse(input).unwrap();
+pub fn part_one(input: &str) -> Result<Answer> {
+    solve_one(parse(input)?)
+}
 
-    let start = std::time::Instant::now();
-    let result = engine.ways_to_win(Constraint(1, 4000)) as i64;
-    let elapsed = start.elapsed();
+pub fn part_two(input: &str) -> Result<Answer> {
+    solve_two(parse(input)?)
+}
 
-    info!(
-        "Traversed inner tree in {}",
-        crate::utils::format_duration(elapsed)
-    );
+fn solve_one(engine: Engine) -> Result<Answer> {
+    Ok(engine.solve().into())
+}
 
-    result
+fn solve_two(engine: Engine) -> Result<Answer> {
+    Ok(engine.ways_to_win(Constraint(1, 4000)).into())
 }
 
 #[cfg(test)]
@@ -43,33 +90,29 @@ mod tests {
     use super::*;
 
     fn input() -> &'static str {
-        r#"px{a<2006:qkq,m>2090:A,rfg}
-        pv{a>1716:R,A}
-        lnx{m>1548:A,A}
-        rfg{s<537:gd,x>2440:R,A}
-        qs{s>3448:A,lnx}
-        qkq{x<1416:A,crn}
-        crn{x>2662:A,R}
-        in{s<1351:px,qqz}
-        qqz{s>2770:qs,m<1801:hdj,R}
-        gd{a>3333:R,R}
-        hdj{m>838:A,pv}
-        
-        {x=787,m=2655,a=1222,s=2876}
-        {x=1679,m=44,a=2067,s=496}
-        {x=2036,m=264,a=79,s=2244}
-        {x=2461,m=1339,a=466,s=291}
-        {x=2127,m=1623,a=2188,s=1013}"#
-            .trim()
+        EXAMPLES[0].input
     }
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(input()), 19114);
+        assert_eq!(part_one(input()).unwrap(), 19114);
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(input()), 167409079868000)
+        assert_eq!(part_two(input()).unwrap(), 167409079868000)
+    }
+
+    #[test]
+    fn test_malformed_part() {
+        let error = part_one("in{x<10:A,R}\n\n{x=787,q=2655}").unwrap_err();
+
+        assert_eq!(
+            error.render(),
+            r#"parse error at line 3, column 8: unexpected `q`, expected `m=`
+  |
+3 | {x=787,q=2655}
+  |        ^"#
+        );
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, Result};
+
 type Workflows = HashMap<String, Vec<Rule>>;
 
 fn convert_workflows(workflows: Vec<Workflow>) -> Workflows {
@@ -281,18 +283,36 @@ pub struct Workflow(pub String, pub Vec<Rule>);
 pub struct Rule(pub Condition, pub WorkflowLabel);
 
 impl Rule {
-    pub fn to_node(&self, workflows: &Workflows) -> Node {
+    /// Expands the rule into a tree, failing if it refers to a workflow that
+    /// does not exist or the workflows refer back to themselves.
+    pub fn to_node(&self, workflows: &Workflows) -> Result<Node> {
+        self.to_node_within(workflows, workflows.len())
+    }
+
+    fn to_node_within(&self, workflows: &Workflows, depth: usize) -> Result<Node> {
         let children = match &self.1 {
             WorkflowLabel::Accepted => vec![Node::Accepted],
             WorkflowLabel::Rejected => vec![Node::Rejected],
             WorkflowLabel::Named(name) => {
-                let rules = workflows.get(name).unwrap();
+                let rules = workflows
+                    .get(name)
+                    .ok_or_else(|| Error::missing(format!("no workflow named {}", name)))?;
+
+                if depth == 0 {
+                    return Err(Error::missing(format!(
+                        "workflow {} never reaches a decision",
+                        name
+                    )));
+                }
 
-                rules.iter().map(|rule| rule.to_node(workflows)).collect()
+                rules
+                    .iter()
+                    .map(|rule| rule.to_node_within(workflows, depth - 1))
+                    .collect::<Result<_>>()?
             }
         };
 
-        Node::Rule(self.0, children)
+        Ok(Node::Rule(self.0, children))
     }
 }
 
@@ -302,19 +322,19 @@ pub struct Engine {
 }
 
 impl Engine {
-    pub fn new(workflows: Vec<Workflow>, parts: Vec<Part<u64>>) -> Self {
+    pub fn new(workflows: Vec<Workflow>, parts: Vec<Part<u64>>) -> Result<Self> {
         let workflows = convert_workflows(workflows);
 
         let root = Node::Root(
             workflows
                 .get("in")
-                .unwrap()
+                .ok_or_else(|| Error::missing("no workflow named in"))?
                 .iter()
                 .map(|rule| rule.to_node(&workflows))
-                .collect(),
+                .collect::<Result<_>>()?,
         );
 
-        Self { parts, root }
+        Ok(Self { parts, root })
     }
 
     pub fn solve(&self) -> u64 {
@@ -625,10 +645,29 @@ mod tests {
             ],
         )];
 
-        let engine = Engine::new(workflows, vec![]);
+        let engine = Engine::new(workflows, vec![]).unwrap();
 
         let result = engine.ways_to_win(constraint);
 
         assert_eq!(result, 4 * 10 * 10 * 10);
     }
+
+    #[test]
+    fn test_engine_workflow_errors() {
+        let workflow = |name: &str, target: &str| {
+            Workflow(
+                name.to_string(),
+                vec![Rule(
+                    Condition::Always,
+                    WorkflowLabel::Named(target.to_string()),
+                )],
+            )
+        };
+
+        assert_eq!(
+            Engine::new(vec![workflow("in", "px")], vec![]).err(),
+            Some(Error::missing("no workflow named px"))
+        );
+        assert!(Engine::new(vec![workflow("in", "px"), workflow("px", "in")], vec![]).is_err());
+    }
 }
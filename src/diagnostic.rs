@@ -0,0 +1,172 @@
+use std::fmt;
+use std::ops::Range;
+
+/// The line of input an error points at, rendered with a caret under the
+/// offending text.
+///
+/// ```
+/// # use adventofcode2023::diagnostic::Snippet;
+/// let input = "Game 1: 3 blue\nGame 2; 4 red";
+/// let snippet = Snippet::locate(input, 21..22);
+///
+/// assert_eq!((snippet.line, snippet.column), (2, 7));
+/// assert_eq!(
+///     snippet.to_string(),
+///     "  |\n2 | Game 2; 4 red\n  |       ^"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snippet {
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// The column in characters, starting at 1.
+    pub column: usize,
+    /// The full text of the line.
+    pub text: String,
+    /// How many characters the caret spans. Always at least one.
+    pub width: usize,
+}
+
+impl Snippet {
+    /// Locates a byte range of the input. Ranges that run past the end of
+    /// the line are cut short, and an empty range still gets a caret.
+    pub fn locate(input: &str, span: Range<usize>) -> Self {
+        let start = char_boundary(input, span.start);
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let end = char_boundary(input, span.end.clamp(start, line_end));
+
+        Self {
+            line: input[..start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            width: input[start..end].chars().count().max(1),
+        }
+    }
+}
+
+/// The closest character boundary at or before `offset`.
+fn char_boundary(input: &str, offset: usize) -> usize {
+    let mut offset = offset.min(input.len());
+
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        // Tabs are kept so the caret lines up however the terminal renders them.
+        let indent = self
+            .text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}{}", gutter, indent, "^".repeat(self.width))
+    }
+}
+
+/// Joins the tokens a grammar expected into a readable suffix for an error
+/// message, or nothing if the grammar did not say.
+pub fn expecting(expected: &[String]) -> String {
+    let expected = expected
+        .iter()
+        .map(|token| describe(token))
+        .collect::<Vec<_>>();
+
+    match &expected[..] {
+        [] => String::new(),
+        [one] => format!(", expected {}", one),
+        [init @ .., last] => format!(", expected one of {} or {}", init.join(", "), last),
+    }
+}
+
+/// LALRPOP names terminals the way they are written in the grammar, so
+/// literals are quoted and regexes are raw strings.
+fn describe(token: &str) -> String {
+    if let Some(pattern) = token
+        .strip_prefix("r#\"")
+        .and_then(|token| token.strip_suffix("\"#"))
+    {
+        format!("text matching `{}`", pattern)
+    } else if let Some(literal) = token
+        .strip_prefix('"')
+        .and_then(|token| token.strip_suffix('"'))
+    {
+        format!("`{}`", literal.replace("\\\"", "\""))
+    } else {
+        token.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_token() {
+        let input = "R 6 (#70c710)\r\nD 5 (#0dc571)";
+        let snippet = Snippet::locate(input, 19..28);
+
+        assert_eq!(
+            snippet,
+            Snippet {
+                line: 2,
+                column: 5,
+                text: "D 5 (#0dc571)".to_string(),
+                width: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_locate_end_of_input() {
+        let input = "seeds: 1 2\n";
+        let snippet = Snippet::locate(input, input.len()..input.len());
+
+        assert_eq!((snippet.line, snippet.column, snippet.width), (2, 1, 1));
+        assert_eq!(snippet.to_string(), "  |\n2 | \n  | ^");
+    }
+
+    #[test]
+    fn test_locate_clamps_to_line() {
+        let input = "ab\ncd";
+        let snippet = Snippet::locate(input, 1..5);
+
+        assert_eq!((snippet.line, snippet.column, snippet.width), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_render_keeps_tabs() {
+        let snippet = Snippet::locate("\tCard é: 1 | x", 14..15);
+
+        assert_eq!(snippet.column, 14);
+        assert_eq!(
+            snippet.to_string().lines().last(),
+            Some("  | \t            ^")
+        );
+    }
+
+    #[test]
+    fn test_expecting() {
+        let tokens = [r#"",""#, r#"";""#, r##"r#"[0-9]+"#"##].map(String::from);
+
+        assert_eq!(expecting(&[]), "");
+        assert_eq!(expecting(&tokens[..1]), ", expected `,`");
+        assert_eq!(
+            expecting(&tokens),
+            ", expected one of `,`, `;` or text matching `[0-9]+`"
+        );
+    }
+}
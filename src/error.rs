@@ -0,0 +1,173 @@
+use std::fmt;
+use std::ops::Range;
+
+use lalrpop_util::ParseError;
+
+use crate::diagnostic::{self, Snippet};
+
+/// Everything that can go wrong while solving a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// The input is malformed at the place the snippet points to.
+    Parse { snippet: Snippet, message: String },
+    /// The input is well-formed but lacks something the puzzle needs.
+    Missing(String),
+    /// A result that must be a whole number is a fraction.
+    NonIntegral { numerator: i128, denominator: i128 },
+    /// The solver does not implement the requested part.
+    UnsupportedPart { day: u8, part: u8 },
+    /// The solver was asked to stop before it finished.
+    Cancelled,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// A parse error at the given byte offset into the input.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        Self::at_span(input, offset..offset, message)
+    }
+
+    /// A parse error covering the given byte range of the input.
+    pub fn at_span(input: &str, span: Range<usize>, message: impl Into<String>) -> Self {
+        Self::Parse {
+            snippet: Snippet::locate(input, span),
+            message: message.into(),
+        }
+    }
+
+    /// A parse error at `token`, which must be a slice of `input`.
+    pub fn at_token(input: &str, token: &str, message: impl Into<String>) -> Self {
+        let start = (token.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+
+        Self::at_span(input, start..start + token.len(), message)
+    }
+
+    pub fn missing(message: impl Into<String>) -> Self {
+        Self::Missing(message.into())
+    }
+
+    /// Converts an error from one of the LALRPOP grammars, locating it in the
+    /// input it was parsing.
+    pub fn from_parser<T: fmt::Display>(input: &str, error: ParseError<usize, T, Error>) -> Self {
+        match error {
+            ParseError::InvalidToken { location } => {
+                Self::at_offset(input, location, "invalid token")
+            }
+            ParseError::UnrecognizedEof { location, expected } => Self::at_offset(
+                input,
+                location,
+                format!(
+                    "unexpected end of input{}",
+                    diagnostic::expecting(&expected)
+                ),
+            ),
+            ParseError::UnrecognizedToken {
+                token: (start, token, end),
+                expected,
+            } => Self::at_span(
+                input,
+                start..end,
+                format!("unexpected `{}`{}", token, diagnostic::expecting(&expected)),
+            ),
+            ParseError::ExtraToken {
+                token: (start, token, end),
+            } => Self::at_span(input, start..end, format!("unexpected extra `{}`", token)),
+            ParseError::User { error } => error,
+        }
+    }
+
+    /// Renders the error for a person to read. Parse errors are followed by
+    /// the line they happened on, with a caret under the offending text.
+    pub fn render(&self) -> String {
+        match self {
+            Self::Parse { snippet, .. } => format!("{}\n{}", self, snippet),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse { snippet, message } => write!(
+                f,
+                "parse error at line {}, column {}: {}",
+                snippet.line, snippet.column, message
+            ),
+            Self::Missing(message) => write!(f, "missing data: {}", message),
+            Self::NonIntegral {
+                numerator,
+                denominator,
+            } => write!(
+                f,
+                "expected a whole number but got {}/{}",
+                numerator, denominator
+            ),
+            Self::UnsupportedPart { day, part } => {
+                write!(f, "day {} has no part {}", day, part)
+            }
+            Self::Cancelled => write!(f, "cancelled before finishing"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_offset() {
+        let input = "first\nsecond\nthird";
+
+        let Error::Parse { snippet, .. } = Error::at_offset(input, 9, "bad") else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((snippet.line, snippet.column), (2, 4));
+    }
+
+    #[test]
+    fn test_at_token() {
+        let input = "R 6 (#70c710)\nX 5 (#0dc571)";
+        let token = input.lines().nth(1).unwrap().split(' ').next().unwrap();
+
+        assert_eq!(
+            Error::at_token(input, token, "unknown direction").to_string(),
+            "parse error at line 2, column 1: unknown direction"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "px{a<2006:qkq,rfg}\n{x=787,m=2655}";
+
+        assert_eq!(
+            Error::at_token(input, &input[22..25], "rating too large").render(),
+            "parse error at line 2, column 4: rating too large\n  |\n2 | {x=787,m=2655}\n  |    ^^^"
+        );
+        assert_eq!(
+            Error::missing("no seeds").render(),
+            "missing data: no seeds"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Error::NonIntegral {
+                numerator: 3,
+                denominator: 2
+            }
+            .to_string(),
+            "expected a whole number but got 3/2"
+        );
+        assert_eq!(
+            Error::UnsupportedPart { day: 4, part: 3 }.to_string(),
+            "day 4 has no part 3"
+        );
+        assert_eq!(Error::Cancelled.to_string(), "cancelled before finishing");
+    }
+}
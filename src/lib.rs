@@ -1,11 +1,51 @@
+pub mod answer;
+pub mod bench;
+pub mod client;
 pub mod day_01;
 pub mod day_02;
+pub mod day_03;
 pub mod day_04;
 pub mod day_05;
 pub mod day_06;
+pub mod day_07;
 pub mod day_08;
 pub mod day_09;
+pub mod day_10;
 pub mod day_15;
 pub mod day_18;
 pub mod day_19;
+pub mod diagnostic;
+pub mod error;
+pub mod manifest;
+pub mod memory;
+pub mod progress;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solver;
+pub mod submit;
 pub mod utils;
+pub mod watch;
+
+use solver::Registry;
+
+/// Builds a registry containing the solver for every implemented day.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register(&day_01::Day01);
+    registry.register(&day_02::Day02);
+    registry.register(&day_03::Day03);
+    registry.register(&day_04::Day04);
+    registry.register(&day_05::Day05);
+    registry.register(&day_06::Day06);
+    registry.register(&day_07::Day07);
+    registry.register(&day_08::Day08);
+    registry.register(&day_09::Day09);
+    registry.register(&day_10::Day10);
+    registry.register(&day_15::Day15);
+    registry.register(&day_18::Day18);
+    registry.register(&day_19::Day19);
+
+    registry
+}
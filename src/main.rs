@@ -1,27 +1,250 @@
+use adventofcode2023::bench::{Baseline, Stats};
+use adventofcode2023::client::{Client, Fetched};
+use adventofcode2023::manifest::{Check, Manifest, Verdict};
+use adventofcode2023::progress::{Reporter, Task, Throttle};
+use adventofcode2023::report::Format;
+use adventofcode2023::runner::{DaySelection, Job, Outcome, PartSelection, Run, Source};
+use adventofcode2023::solver::Example;
+use adventofcode2023::submit::{Attempt, Feedback, Log};
+use adventofcode2023::watch::Watcher;
 use adventofcode2023::*;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+use std::io::IsTerminal;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::thread;
+use std::time::Duration;
 
 #[cfg(feature = "logging")]
-use tracing::{error, info};
+use tracing::{debug, error, info};
+
+#[cfg(feature = "alloc-metrics")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
 
 /// Advent of Code 2023 - A Rust CLI for solving Advent of Code 2023 puzzles.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
-    /// The puzzle day to solve.
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
+
+    /// The verbosity level. Default is info. Set once for debug, twice or more for trace. Requires the `logging` feature.
+    #[arg(short = 'v', long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Time repeated runs of one or more solvers.
+    Bench(BenchArgs),
+
+    /// Download a day's puzzle input into `inputs/`, unless it is already there.
+    Fetch(FetchArgs),
+
+    /// Solve one part of a day and submit the answer.
+    Submit(SubmitArgs),
+
+    /// Generate the module for a new day and register it.
+    New(NewArgs),
+}
+
+/// Draws solver progress on stderr as a single line that is redrawn in place,
+/// and also emits it as debug events when logging.
+struct ProgressLine {
+    throttle: Throttle,
+    drawn: AtomicBool,
+}
+
+impl ProgressLine {
+    fn new() -> Self {
+        Self {
+            throttle: Throttle::new(Duration::from_millis(100)),
+            drawn: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Reporter for ProgressLine {
+    fn update(&self, task: Option<Task>, completed: u64, total: u64) {
+        if !self.throttle.ready() {
+            return;
+        }
+
+        let message = progress::describe(task, completed, total);
+
+        #[cfg(feature = "logging")]
+        {
+            debug!(completed, total, "{}", message);
+        }
+
+        eprint!("\r\x1b[2K{}", message);
+        self.drawn.store(true, AtomicOrdering::Relaxed);
+    }
+
+    fn finish(&self, _task: Task) {
+        if self.drawn.swap(false, AtomicOrdering::Relaxed) {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+struct SolveArgs {
+    /// The puzzle day to solve, or `all` to solve every implemented day.
+    day: DaySelection,
+
+    /// The part of the puzzle to solve, or `both`.
+    #[arg(short = 'p', long, default_value = "1")]
+    part: PartSelection,
+
+    /// The input file to use, `-` for stdin, or a directory of inputs. Repeat to solve several
+    /// inputs. Cannot be combined with `all`. [default: `inputs/{day:02}.txt`]
+    #[arg(short = 'f', long)]
+    file: Vec<String>,
+
+    /// Compare the answers against the answers manifest instead of printing them.
+    #[arg(long, conflicts_with = "file")]
+    check: bool,
+
+    /// The answers manifest to check against.
+    #[arg(long, default_value = manifest::DEFAULT_PATH)]
+    answers: String,
+
+    /// Solve the puzzle's worked example instead of the input, or the example
+    /// with the given name or number when a day has several, and compare
+    /// against its answers.
+    #[arg(long, value_name = "NAME", num_args = 0..=1, default_missing_value = "1", conflicts_with_all = ["file", "check"])]
+    example: Option<String>,
+
+    /// Keep running and solve again whenever an input file changes.
+    #[arg(long, conflicts_with_all = ["check", "example"])]
+    watch: bool,
+
+    /// Give up on any solver that takes longer than this many seconds.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds, conflicts_with = "example")]
+    timeout: Option<Duration>,
+
+    /// How many solvers to run at once when solving `all`, several inputs or `--check`. Solvers
+    /// running at once compete for the CPU, so use `--jobs 1` for accurate timings. [default: the
+    /// number of CPUs]
+    #[arg(short = 'j', long)]
+    jobs: Option<NonZeroUsize>,
+
+    /// How to print the results: `text`, or `json` or `csv` records for scripts.
+    #[arg(short = 'o', long, default_value = "text", conflicts_with_all = ["check", "example", "watch"])]
+    output: Format,
+
+    /// How often to check the input files for changes, in milliseconds.
+    #[arg(long, default_value_t = watch::DEFAULT_INTERVAL, requires = "watch")]
+    interval: u64,
+}
+
+#[derive(Args, Debug)]
+struct BenchArgs {
+    /// The puzzle day to benchmark, or `all` to benchmark every implemented day.
+    day: DaySelection,
+
+    /// The part of the puzzle to benchmark, or `both`.
+    #[arg(short = 'p', long, default_value = "1")]
+    part: PartSelection,
+
+    /// The input file to use, or `-` for stdin. Cannot be combined with `all`. [default: `inputs/{day:02}.txt`]
+    #[arg(short = 'f', long)]
+    file: Option<String>,
+
+    /// The number of timed runs of each solver.
+    #[arg(short = 'n', long, default_value = "10")]
+    iterations: usize,
+
+    /// The number of untimed runs of each solver before timing starts.
+    #[arg(short = 'w', long, default_value = "3")]
+    warmup: usize,
+
+    /// Save the median timings as the new baselines.
+    #[arg(long)]
+    save: bool,
+
+    /// Compare the median timings against the saved baselines instead of printing them.
+    #[arg(long, conflicts_with = "save")]
+    compare: bool,
+
+    /// The directory holding one baseline file per day and part.
+    #[arg(long, default_value = bench::DEFAULT_BASELINE_DIR)]
+    baselines: String,
+
+    /// How many percent slower than its baseline a solve may get before it is flagged.
+    #[arg(long, default_value_t = bench::DEFAULT_THRESHOLD, requires = "compare")]
+    threshold: f64,
+}
+
+#[derive(Args, Debug)]
+struct FetchArgs {
+    /// The puzzle day to fetch.
     day: u8,
 
-    /// The part of the puzzle to solve.
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Args, Debug)]
+struct SubmitArgs {
+    /// The puzzle day to submit an answer for.
+    day: u8,
+
+    /// The part of the puzzle to submit an answer for.
     #[arg(short = 'p', long, default_value = "1")]
     part: u8,
 
-    /// The input file to use. [default: `input/{day}.txt`]
+    /// The input file to use, or `-` for stdin. [default: `inputs/{day:02}.txt`]
     #[arg(short = 'f', long)]
     file: Option<String>,
 
-    /// The verbosity level. Default is info. Set once for debug, twice or more for trace. Requires the `logging` feature.
-    #[arg(short = 'v', long, action = clap::ArgAction::Count)]
-    verbose: u8,
+    /// The log of earlier submissions, used to avoid repeating answers.
+    #[arg(long, default_value = submit::DEFAULT_LOG_PATH)]
+    log: String,
+
+    /// The minimum number of seconds between two submissions.
+    #[arg(long, default_value_t = submit::DEFAULT_COOLDOWN)]
+    cooldown: u64,
+
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Args, Debug)]
+struct NewArgs {
+    /// The puzzle day to generate.
+    day: u8,
+
+    /// Also generate an AST and a LALRPOP grammar for the input.
+    #[arg(long)]
+    grammar: bool,
+}
+
+/// How to reach the puzzle site.
+#[derive(Args, Debug)]
+struct SiteArgs {
+    /// The base URL of the puzzle site.
+    #[arg(long, default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// A file containing the session token. [default: the `AOC_SESSION` environment variable]
+    #[arg(long)]
+    session_file: Option<String>,
+}
+
+impl SiteArgs {
+    fn connect(&self) -> Result<Client, String> {
+        let session = client::session(self.session_file.as_deref())?;
+
+        Ok(Client::new(&self.base_url, session))
+    }
 }
 
 fn main() {
@@ -43,222 +266,743 @@ fn main() {
             .finish();
 
         tracing::subscriber::set_global_default(subscriber).unwrap();
+    }
 
-        info!("Solving day {} part {}...", args.day, args.part);
+    // Progress is only worth drawing for a person watching.
+    if std::io::stderr().is_terminal() {
+        progress::set_reporter(ProgressLine::new()).unwrap();
     }
-    #[cfg(not(feature = "logging"))]
-    {
-        println!("Solving day {} part {}...", args.day, args.part)
+
+    match args.command {
+        Some(Command::Bench(args)) => bench(args),
+        Some(Command::Fetch(args)) => fetch(args),
+        Some(Command::Submit(args)) => submit(args),
+        Some(Command::New(args)) => new(args),
+        None => solve(args.solve.expect("a day is required without a subcommand")),
     }
+}
 
-    let input_file = args
-        .file
-        .clone() // Clone so there is no partial move
-        .unwrap_or(format!("inputs/{:02}.txt", args.day));
-    let input = match std::fs::read_to_string(input_file.as_str()) {
-        Ok(input) => input,
-        Err(e) => {
-            #[cfg(feature = "logging")]
-            {
-                error!(?args, ?input_file, "Error reading input file: {}", e);
-            }
-            #[cfg(not(feature = "logging"))]
-            {
-                eprintln!("Error reading input file '{}': {}", input_file.as_str(), e);
+fn solve(args: SolveArgs) {
+    // Records are the only thing printed when a script is reading them.
+    if args.output == Format::Text {
+        #[cfg(feature = "logging")]
+        {
+            info!("Solving day {} part {}...", args.day, args.part);
+        }
+        #[cfg(not(feature = "logging"))]
+        {
+            println!("Solving day {} part {}...", args.day, args.part)
+        }
+    }
+
+    let registry = registry();
+    let jobs = select_jobs(&registry, args.day, args.part, !args.file.is_empty());
+    let threads = args.jobs.map_or_else(
+        || thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        NonZeroUsize::get,
+    );
+
+    if args.check {
+        std::panic::set_hook(Box::new(|_| {}));
+
+        let manifest = match Manifest::load(&args.answers) {
+            Ok(manifest) => manifest,
+            Err(message) => exit_with_error(message),
+        };
+
+        exit_with_check_report(&manifest.check(&jobs, threads, args.timeout));
+    }
+
+    if let Some(selector) = args.example {
+        if let DaySelection::Day(day) = args.day {
+            if Example::find(jobs[0].solver.examples(), &selector).is_none() {
+                exit_with_error(format!("Day {} has no example {}", day, selector));
             }
-            std::process::exit(1);
         }
+
+        std::panic::set_hook(Box::new(|_| {}));
+
+        exit_with_check_report(&manifest::check_examples(&jobs, &selector));
+    }
+
+    let DaySelection::Day(day) = args.day else {
+        if args.watch {
+            exit_with_error("--watch cannot be combined with `all`".to_string());
+        }
+
+        // Panics are reported in the summary table instead.
+        std::panic::set_hook(Box::new(|_| {}));
+
+        exit_with_summary(&runner::run_all(&jobs, threads, args.timeout), args.output);
+    };
+
+    let sources = if args.file.is_empty() {
+        vec![Source::File(runner::input_path(day))]
+    } else {
+        runner::sources(&args.file).unwrap_or_else(|message| exit_with_error(message))
+    };
+
+    if args.watch {
+        watch(
+            &jobs,
+            &sources,
+            Duration::from_millis(args.interval),
+            threads,
+            args.timeout,
+        );
+    }
+
+    let (PartSelection::Part(part), [source], Format::Text) =
+        (args.part, &sources[..], args.output)
+    else {
+        std::panic::set_hook(Box::new(|_| {}));
+
+        exit_with_summary(
+            &runner::run_sources(&jobs, &sources, threads, args.timeout),
+            args.output,
+        );
     };
 
-    // Start the timer only if opting into timing through a feature flag.
+    let input = read_input(source);
+    let solver = jobs[0].solver;
+    let run = move || progress::run_as(Task { day, part }, || solver.run(part, &input));
+
+    let result = match args.timeout {
+        Some(timeout) => runner::with_timeout(timeout, run).unwrap_or_else(|elapsed| {
+            exit_with_error(format!(
+                "Day {} part {} timed out after {}",
+                day,
+                part,
+                utils::format_duration(elapsed)
+            ))
+        }),
+        None => run(),
+    };
+
+    let solution = match result {
+        Ok(solution) => solution,
+        Err(e) => exit_with_error(format!("Day {} part {} failed: {}", day, part, e.render())),
+    };
+    let result = solution.answer;
+
+    #[cfg(feature = "logging")]
+    {
+        info!("Result: {}", result);
+    }
+    #[cfg(not(feature = "logging"))]
+    {
+        println!("Result: {}", result);
+    }
+
     #[cfg(feature = "metrics")]
-    let start = std::time::SystemTime::now();
-
-    let result = match args.day {
-        1 => match args.part {
-            1 => day_01::part_one(&input),
-            2 => day_01::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
-                }
-                std::process::exit(1);
-            }
-        },
-        2 => match args.part {
-            1 => day_02::part_one(&input).into(),
-            2 => day_02::part_two(&input).into(),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
-                }
-                std::process::exit(1);
-            }
-        },
-        4 => match args.part {
-            1 => day_04::part_one(&input),
-            // 2 => day_04::part_two(&input).into(),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
+    {
+        let parse = utils::format_duration(solution.timings.parse);
+        let solve = utils::format_duration(solution.timings.solve);
+        let total = utils::format_duration(solution.timings.total());
+
+        #[cfg(feature = "logging")]
+        {
+            info!("Parsed input in {}", parse);
+            info!("Solved puzzle in {}", solve);
+            info!("Solution found in {}", total);
+        }
+        #[cfg(not(feature = "logging"))]
+        {
+            println!("Parsed input in {}", parse);
+            println!("Solved puzzle in {}", solve);
+            println!("Solution found in {}", total);
+        }
+    }
+
+    #[cfg(feature = "alloc-metrics")]
+    {
+        let memory = format!(
+            "Allocated {} in {} allocations, peaking at {} live",
+            utils::format_bytes(solution.memory.bytes),
+            solution.memory.allocations,
+            utils::format_bytes(solution.memory.peak)
+        );
+
+        #[cfg(feature = "logging")]
+        {
+            info!("{}", memory);
+        }
+        #[cfg(not(feature = "logging"))]
+        {
+            println!("{}", memory);
+        }
+    }
+}
+
+/// Solves the jobs against every source, then again each time one of the
+/// sources changes. Never returns.
+fn watch(
+    jobs: &[Job],
+    sources: &[Source],
+    interval: Duration,
+    threads: usize,
+    timeout: Option<Duration>,
+) -> ! {
+    let paths = sources
+        .iter()
+        .map(|source| match source {
+            Source::File(path) => PathBuf::from(path),
+            Source::Stdin => exit_with_error("--watch needs input files, not stdin".to_string()),
+        })
+        .collect::<Vec<_>>();
+
+    // Failures are reported with the answers instead.
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut watcher = Watcher::new(paths);
+
+    loop {
+        for run in runner::run_sources(jobs, sources, threads, timeout) {
+            let input = run.input.as_ref().map_or_else(String::new, |input| {
+                if sources.len() > 1 {
+                    format!(" ({})", input)
+                } else {
+                    String::new()
                 }
-                std::process::exit(1);
-            }
-        },
-        5 => match args.part {
-            1 => day_05::part_one(&input),
-            2 => day_05::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
+            });
+
+            match run.outcome {
+                Outcome::Solved {
+                    answer, timings, ..
+                } => {
+                    let message = format!(
+                        "Day {} part {}{}: {} (parsed in {}, solved in {})",
+                        run.day,
+                        run.part,
+                        input,
+                        answer,
+                        utils::format_duration(timings.parse),
+                        utils::format_duration(timings.solve)
+                    );
+
+                    #[cfg(feature = "logging")]
+                    {
+                        info!("{}", message);
+                    }
+                    #[cfg(not(feature = "logging"))]
+                    {
+                        println!("{}", message);
+                    }
                 }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
+                failed => {
+                    let message = format!(
+                        "Day {} part {}{} failed: {}",
+                        run.day, run.part, input, failed
+                    );
+
+                    #[cfg(feature = "logging")]
+                    {
+                        error!("{}", message);
+                    }
+                    #[cfg(not(feature = "logging"))]
+                    {
+                        eprintln!("{}", message);
+                    }
                 }
-                std::process::exit(1);
             }
-        },
-        6 => match args.part {
-            1 => day_06::part_one(&input),
-            2 => day_06::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
-                }
-                std::process::exit(1);
+        }
+
+        #[cfg(feature = "logging")]
+        {
+            info!("Watching for changes...");
+        }
+        #[cfg(not(feature = "logging"))]
+        {
+            println!("Watching for changes...");
+        }
+
+        watcher.wait(interval);
+    }
+}
+
+fn bench(args: BenchArgs) {
+    let registry = registry();
+    let jobs = select_jobs(&registry, args.day, args.part, args.file.is_some());
+
+    std::panic::set_hook(Box::new(|_| {}));
+
+    // Read a given file up front, as stdin can only be read once.
+    let file = args.file.map(|arg| read_input(&Source::from_arg(&arg)));
+
+    // Days without a downloaded input are skipped, as when solving `all`.
+    let results = jobs
+        .iter()
+        .map(|job| {
+            let input = match (&file, job.default_input()) {
+                (Some(input), _) => input.clone(),
+                (None, Some(source)) => read_input(&source),
+                (None, None) => return (job, None),
+            };
+
+            #[cfg(feature = "logging")]
+            {
+                info!("Benchmarking day {} part {}...", job.day(), job.part);
             }
-        },
-        8 => match args.part {
-            1 => day_08::part_one(&input),
-            2 => day_08::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
-                }
-                std::process::exit(1);
+
+            (
+                job,
+                Some(bench::bench(job, &input, args.warmup, args.iterations)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if args.compare {
+        exit_with_comparison(&results, &args.baselines, args.threshold);
+    }
+
+    let rows = results
+        .iter()
+        .map(|(job, result)| match result {
+            Some(Ok(stats)) => [
+                job.day().to_string(),
+                job.part.to_string(),
+                stats.runs.to_string(),
+                utils::format_duration(stats.min),
+                utils::format_duration(stats.mean),
+                utils::format_duration(stats.median),
+                utils::format_duration(stats.p95),
+                utils::format_duration(stats.std_dev),
+            ],
+            Some(Err(message)) => {
+                let mut row = <[String; 8]>::default();
+                row[0] = job.day().to_string();
+                row[1] = job.part.to_string();
+                row[2] = format!("error: {}", message);
+                row
             }
-        },
-        9 => match args.part {
-            1 => day_09::part_one(&input),
-            2 => day_09::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
-                }
-                std::process::exit(1);
+            None => {
+                let mut row = <[String; 8]>::default();
+                row[0] = job.day().to_string();
+                row[1] = job.part.to_string();
+                row[2] = "skipped: no input".to_string();
+                row
             }
-        },
-        15 => match args.part {
-            1 => day_15::part_one(&input),
-            // 2 => day_15::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
-                }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
-                }
-                std::process::exit(1);
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "{}",
+        utils::format_table(
+            ["Day", "Part", "Runs", "Min", "Mean", "Median", "p95", "Std dev"],
+            &rows
+        )
+    );
+
+    if args.save {
+        for (job, result) in &results {
+            let Some(Ok(stats)) = result else {
+                continue;
+            };
+
+            match Baseline::new(job.day(), job.part, stats).save(&args.baselines) {
+                Ok(path) => println!(
+                    "Saved baseline for day {} part {} to {}",
+                    job.day(),
+                    job.part,
+                    path.display()
+                ),
+                Err(message) => exit_with_error(message),
             }
-        },
-        18 => match args.part {
-            1 => day_18::part_one(&input),
-            2 => day_18::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
+        }
+    }
+
+    if results
+        .iter()
+        .any(|(_, result)| matches!(result, Some(Err(_))))
+    {
+        std::process::exit(1);
+    }
+}
+
+/// Prints how every benchmark compares with its saved baseline and exits,
+/// with a non-zero status if any solve got slower than `threshold` percent or
+/// failed. Jobs that were skipped have no result.
+fn exit_with_comparison(
+    results: &[(&Job, Option<Result<Stats, String>>)],
+    baselines: &str,
+    threshold: f64,
+) -> ! {
+    let mut failures = 0;
+
+    let rows = results
+        .iter()
+        .map(|(job, result)| {
+            let mut row = <[String; 6]>::default();
+            row[0] = job.day().to_string();
+            row[1] = job.part.to_string();
+
+            let stats = match result {
+                Some(Ok(stats)) => stats,
+                Some(Err(message)) => {
+                    failures += 1;
+                    row[5] = format!("error: {}", message);
+                    return row;
                 }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
+                None => {
+                    row[5] = "skipped: no input".to_string();
+                    return row;
                 }
-                std::process::exit(1);
-            }
-        },
-        19 => match args.part {
-            1 => day_19::part_one(&input),
-            2 => day_19::part_two(&input),
-            _ => {
-                #[cfg(feature = "logging")]
-                {
-                    error!(?args, "Invalid part number",);
+            };
+
+            row[2] = utils::format_duration(stats.median);
+
+            match Baseline::load(baselines, job.day(), job.part) {
+                Ok(Some(baseline)) => {
+                    let comparison = baseline.compare(stats);
+                    let slower = comparison.is_regression(threshold);
+
+                    if slower {
+                        failures += 1;
+                    }
+
+                    row[3] = utils::format_duration(comparison.baseline);
+                    row[4] = format!("{:+.1}%", comparison.change());
+                    row[5] = if slower { "SLOWER" } else { "ok" }.to_string();
                 }
-                #[cfg(not(feature = "logging"))]
-                {
-                    eprintln!("Invalid part number: {}", args.part);
+                Ok(None) => row[5] = "no baseline".to_string(),
+                Err(message) => {
+                    failures += 1;
+                    row[5] = format!("error: {}", message);
                 }
-                std::process::exit(1);
             }
-        },
-        _ => {
-            #[cfg(feature = "logging")]
-            {
-                error!(?args, "Invalid day number");
-            }
-            #[cfg(not(feature = "logging"))]
-            {
-                eprintln!("Invalid day number: {}", args.day);
-            }
-            std::process::exit(1);
-        }
-    };
 
-    #[cfg(feature = "metrics")]
-    let elapsed = start.elapsed().unwrap();
+            row
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "{}",
+        utils::format_table(
+            ["Day", "Part", "Median", "Baseline", "Change", "Status"],
+            &rows
+        )
+    );
+
+    if failures > 0 {
+        eprintln!(
+            "{} of {} benchmarks failed or got more than {}% slower",
+            failures,
+            results.len(),
+            threshold
+        );
+        std::process::exit(1);
+    }
+
+    std::process::exit(0);
+}
+
+fn fetch(args: FetchArgs) {
+    let path = runner::input_path(args.day);
+
+    let message = match client::fetch(args.day, &path, || args.site.connect()) {
+        Ok(Fetched::Cached) => format!("Input for day {} is already at {}", args.day, path),
+        Ok(Fetched::Downloaded) => format!("Saved input for day {} to {}", args.day, path),
+        Err(message) => exit_with_error(message),
+    };
 
     #[cfg(feature = "logging")]
     {
-        info!("Result: {}", result);
+        info!("{}", message);
     }
     #[cfg(not(feature = "logging"))]
     {
-        println!("Result: {}", result);
+        println!("{}", message);
     }
+}
 
-    #[cfg(feature = "metrics")]
+fn submit(args: SubmitArgs) {
+    let (day, part) = (args.day, args.part);
+
+    let registry = registry();
+    let jobs = select_jobs(
+        &registry,
+        DaySelection::Day(day),
+        PartSelection::Part(part),
+        args.file.is_some(),
+    );
+
+    let source = args
+        .file
+        .as_deref()
+        .map_or_else(|| Source::File(runner::input_path(day)), Source::from_arg);
+    let input = read_input(&source);
+
+    let answer = match jobs[0].solver.run(part, &input) {
+        Ok(solution) => solution.answer,
+        Err(e) => exit_with_error(format!("Day {} part {} failed: {}", day, part, e.render())),
+    };
+
+    let mut log = Log::load(&args.log).unwrap_or_else(|message| exit_with_error(message));
+    let now = submit::now();
+
+    if let Some(refusal) = log.refusal(day, part, &answer, now, args.cooldown) {
+        exit_with_error(format!("Not submitting {}: {}", answer, refusal));
+    }
+
+    let reply = args
+        .site
+        .connect()
+        .and_then(|client| client.submit(day, part, &answer))
+        .unwrap_or_else(|message| exit_with_error(message));
+
+    let attempt = Attempt {
+        day,
+        part,
+        answer,
+        feedback: reply.feedback,
+        at: now,
+        wait: reply.wait,
+    };
+
+    let mut message = format!(
+        "Day {} part {}: submitted {}, {}",
+        day, part, attempt.answer, attempt.feedback
+    );
+
+    if let Some(wait) = reply.wait {
+        message.push_str(&format!(" (wait {}s before the next submission)", wait));
+    }
+
+    if let Err(e) = log.append(&args.log, attempt) {
+        exit_with_error(format!("{}\n{}", message, e));
+    }
+
+    if reply.feedback != Feedback::Correct {
+        exit_with_error(message);
+    }
+
+    #[cfg(feature = "logging")]
+    {
+        info!("{}", message);
+    }
+    #[cfg(not(feature = "logging"))]
     {
+        println!("{}", message);
+    }
+}
+
+fn new(args: NewArgs) {
+    let files = match scaffold::generate(Path::new(""), args.day, args.grammar) {
+        Ok(files) => files,
+        Err(message) => exit_with_error(message),
+    };
+
+    for file in files {
         #[cfg(feature = "logging")]
         {
-            info!("Solution found in {}", utils::format_duration(elapsed));
+            info!("Created {}", file.display());
         }
         #[cfg(not(feature = "logging"))]
         {
-            println!("Solution found in {}", utils::format_duration(elapsed));
+            println!("Created {}", file.display());
         }
     }
 }
+
+/// Parses a positive number of seconds, which may be fractional.
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!(
+            "expected a positive number of seconds, got `{}`",
+            s
+        )),
+    }
+}
+
+/// Resolves the selected jobs, exiting if the selection is invalid.
+fn select_jobs(
+    registry: &solver::Registry,
+    day: DaySelection,
+    part: PartSelection,
+    has_files: bool,
+) -> Vec<Job> {
+    if day == DaySelection::All && has_files {
+        exit_with_error("An input file cannot be given when solving all days");
+    }
+
+    match runner::select(registry, day, part) {
+        Ok(jobs) => jobs,
+        Err(message) => exit_with_error(message),
+    }
+}
+
+/// Reads a puzzle input, exiting if it cannot be read.
+fn read_input(source: &Source) -> String {
+    match source.read() {
+        Ok(input) => input,
+        Err(message) => {
+            #[cfg(feature = "logging")]
+            {
+                error!(%source, "Error reading input: {}", message);
+            }
+            #[cfg(not(feature = "logging"))]
+            {
+                eprintln!("Error reading input: {}", message);
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn exit_with_error(message: impl std::fmt::Display) -> ! {
+    #[cfg(feature = "logging")]
+    {
+        error!("{}", message);
+    }
+    #[cfg(not(feature = "logging"))]
+    {
+        eprintln!("{}", message);
+    }
+    std::process::exit(1);
+}
+
+/// Prints the runs in the given format and exits, with a non-zero status if
+/// any of them failed.
+fn exit_with_summary(runs: &[Run], format: Format) -> ! {
+    match format {
+        Format::Text => print_summary(runs),
+        Format::Json => println!("{}", report::json(runs)),
+        Format::Csv => print!("{}", report::csv(runs)),
+    }
+
+    let failures = runs.iter().filter(|run| run.outcome.is_failed()).count();
+
+    if failures > 0 {
+        eprintln!("{} of {} solvers failed", failures, runs.len());
+        std::process::exit(1);
+    }
+
+    std::process::exit(0);
+}
+
+/// Prints a table with the answer and timings of every run.
+fn print_summary(runs: &[Run]) {
+    let rows = runs
+        .iter()
+        .map(|run| {
+            let (answer, times) = match &run.outcome {
+                Outcome::Solved {
+                    answer, timings, ..
+                } => (
+                    answer.to_string(),
+                    [timings.parse, timings.solve, timings.total()].map(utils::format_duration),
+                ),
+                Outcome::Failed(message) => (
+                    format!("error: {}", message),
+                    ["-", "-", "-"].map(String::from),
+                ),
+                Outcome::Skipped(reason) => (
+                    format!("skipped: {}", reason),
+                    ["-", "-", "-"].map(String::from),
+                ),
+            };
+
+            let [parse, solve, total] = times;
+
+            #[cfg(feature = "alloc-metrics")]
+            let [allocations, allocated, peak] = match &run.outcome {
+                Outcome::Solved { memory, .. } => [
+                    memory.allocations.to_string(),
+                    utils::format_bytes(memory.bytes),
+                    utils::format_bytes(memory.peak),
+                ],
+                _ => ["-", "-", "-"].map(String::from),
+            };
+
+            [
+                run.day.to_string(),
+                run.part.to_string(),
+                run.input
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), Source::to_string),
+                answer,
+                parse,
+                solve,
+                total,
+                #[cfg(feature = "alloc-metrics")]
+                allocations,
+                #[cfg(feature = "alloc-metrics")]
+                allocated,
+                #[cfg(feature = "alloc-metrics")]
+                peak,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let headers = [
+        "Day",
+        "Part",
+        "Input",
+        "Answer",
+        "Parse",
+        "Solve",
+        "Time",
+        #[cfg(feature = "alloc-metrics")]
+        "Allocs",
+        #[cfg(feature = "alloc-metrics")]
+        "Allocated",
+        #[cfg(feature = "alloc-metrics")]
+        "Peak",
+    ];
+
+    println!("{}", utils::format_table(headers, &rows));
+}
+
+/// Prints a table of the checked answers and exits, with a non-zero status if
+/// any answer did not match the manifest.
+fn exit_with_check_report(checks: &[Check]) -> ! {
+    let rows = checks
+        .iter()
+        .map(|check| {
+            let (status, expected, actual) = match &check.verdict {
+                Verdict::Pass => ("pass", String::new(), String::new()),
+                Verdict::Fail { expected, actual } => {
+                    ("FAIL", expected.to_string(), actual.clone())
+                }
+                Verdict::Missing => ("missing", String::new(), String::new()),
+            };
+
+            [
+                check.day.to_string(),
+                check.part.to_string(),
+                check.input.clone().unwrap_or_else(|| "-".to_string()),
+                status.to_string(),
+                expected,
+                actual,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "{}",
+        utils::format_table(
+            ["Day", "Part", "Input", "Status", "Expected", "Actual"],
+            &rows
+        )
+    );
+
+    let failures = checks.iter().filter(|check| check.is_failure()).count();
+    let missing = checks
+        .iter()
+        .filter(|check| check.verdict == Verdict::Missing)
+        .count();
+
+    println!(
+        "{} passed, {} failed, {} missing",
+        checks.len() - failures - missing,
+        failures,
+        missing
+    );
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
+
+    std::process::exit(0);
+}
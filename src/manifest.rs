@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::runner::{self, Job, Outcome};
+use crate::solver::Example;
+
+/// The default location of the answers manifest.
+pub const DEFAULT_PATH: &str = "answers.json";
+
+/// The known-correct answer for one part of a day on a given input.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Expected {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Answer,
+}
+
+/// A checked-in record of known-correct answers, used to catch refactors
+/// that silently change results.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    entries: Vec<Expected>,
+}
+
+impl Manifest {
+    pub fn new(entries: Vec<Expected>) -> Self {
+        Self { entries }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json)
+            .map(Self::new)
+            .map_err(|e| format!("invalid answers manifest: {}", e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read answers manifest {}: {}", path, e))?;
+
+        Self::from_json(&json)
+    }
+
+    /// The expected answers recorded for one part of a day.
+    pub fn expected(&self, day: u8, part: u8) -> impl Iterator<Item = &Expected> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.day == day && entry.part == part)
+    }
+
+    /// Runs every job against each input recorded for it and compares the
+    /// answers, on up to `threads` threads at once, giving up on any solver
+    /// that takes longer than `timeout`. Jobs without a recorded answer are
+    /// reported as missing.
+    pub fn check(&self, jobs: &[Job], threads: usize, timeout: Option<Duration>) -> Vec<Check> {
+        let entries = jobs
+            .iter()
+            .flat_map(|job| {
+                let expected = self.expected(job.day(), job.part).map(Some);
+                let expected = expected.collect::<Vec<_>>();
+
+                if expected.is_empty() {
+                    vec![(job, None)]
+                } else {
+                    expected.into_iter().map(|entry| (job, entry)).collect()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        runner::map_parallel(&entries, threads, |&(job, expected)| match expected {
+            Some(expected) => Check::run(job, expected, timeout),
+            None => Check {
+                day: job.day(),
+                part: job.part,
+                input: None,
+                verdict: Verdict::Missing,
+            },
+        })
+    }
+}
+
+/// Runs every job against the example of its day picked by `selector`, a
+/// name or a number counting from 1, and compares the answers with the ones
+/// the example gives. Jobs whose day has no such example, or whose part the
+/// example does not cover, are reported as missing.
+pub fn check_examples(jobs: &[Job], selector: &str) -> Vec<Check> {
+    jobs.iter()
+        .map(|job| {
+            let example = Example::find(job.solver.examples(), selector);
+            let label = format!("example {}", example.map_or(selector, |e| e.name));
+
+            match example.and_then(|example| Some((example, example.answer(job.part)?))) {
+                Some((example, expected)) => {
+                    Check::compare(job, &label, &expected, job.run(example.input).outcome)
+                }
+                None => Check {
+                    day: job.day(),
+                    part: job.part,
+                    input: Some(label),
+                    verdict: Verdict::Missing,
+                },
+            }
+        })
+        .collect()
+}
+
+/// How a computed answer compared against the manifest.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer, actual: String },
+    Missing,
+}
+
+/// The result of checking one manifest entry.
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub verdict: Verdict,
+}
+
+impl Check {
+    fn run(job: &Job, expected: &Expected, timeout: Option<Duration>) -> Self {
+        let actual = match std::fs::read_to_string(&expected.input) {
+            Ok(input) => job.run_with_timeout(&input, timeout).outcome,
+            Err(e) => Outcome::Failed(format!("could not read {}: {}", expected.input, e)),
+        };
+
+        Self::compare(job, &expected.input, &expected.answer, actual)
+    }
+
+    fn compare(job: &Job, input: &str, expected: &Answer, actual: Outcome) -> Self {
+        let verdict = match actual {
+            Outcome::Solved { answer, .. } if answer == *expected => Verdict::Pass,
+            Outcome::Solved { answer, .. } => Verdict::Fail {
+                expected: expected.clone(),
+                actual: answer.to_string(),
+            },
+            Outcome::Failed(message) => Verdict::Fail {
+                expected: expected.clone(),
+                actual: format!("error: {}", message),
+            },
+            Outcome::Skipped(reason) => Verdict::Fail {
+                expected: expected.clone(),
+                actual: format!("skipped: {}", reason),
+            },
+        };
+
+        Self {
+            day: job.day(),
+            part: job.part,
+            input: Some(input.to_string()),
+            verdict,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self.verdict, Verdict::Fail { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{DaySelection, PartSelection};
+
+    fn manifest() -> Manifest {
+        Manifest::from_json(
+            r#"[
+                { "day": 6, "part": 1, "input": "inputs/06.txt", "answer": 2065338 },
+                { "day": 6, "part": 2, "input": "inputs/06.txt", "answer": 1 },
+                { "day": 6, "part": 2, "input": "inputs/00.txt", "answer": 1 }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_verdicts() {
+        let registry = crate::registry();
+        let jobs = runner::select(&registry, DaySelection::Day(6), PartSelection::Both).unwrap();
+
+        let checks = manifest().check(&jobs, 2, None);
+
+        assert_eq!(checks.len(), 3);
+        assert_eq!(checks[0].verdict, Verdict::Pass);
+        assert_eq!(
+            checks[1].verdict,
+            Verdict::Fail {
+                expected: Answer::from(1i64),
+                actual: "34934171".to_string()
+            }
+        );
+        assert!(checks[2].is_failure());
+    }
+
+    #[test]
+    fn test_check_missing() {
+        let registry = crate::registry();
+        let jobs = runner::select(&registry, DaySelection::Day(9), PartSelection::Part(1)).unwrap();
+
+        let checks = manifest().check(&jobs, 2, None);
+
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].verdict, Verdict::Missing);
+        assert!(!checks[0].is_failure());
+    }
+
+    #[test]
+    fn test_check_timeout() {
+        let registry = crate::registry();
+        let jobs = runner::select(&registry, DaySelection::Day(5), PartSelection::Part(2)).unwrap();
+        let manifest = Manifest::from_json(
+            r#"[{ "day": 5, "part": 2, "input": "inputs/05.txt", "answer": 23738616 }]"#,
+        )
+        .unwrap();
+
+        let checks = manifest.check(&jobs, 1, Some(Duration::from_millis(1)));
+
+        match &checks[0].verdict {
+            Verdict::Fail { actual, .. } => assert!(actual.starts_with("error: timed out after")),
+            verdict => panic!("expected a timeout, got {:?}", verdict),
+        }
+    }
+
+    #[test]
+    fn test_check_examples() {
+        let registry = crate::registry();
+        let jobs = runner::select(&registry, DaySelection::Day(1), PartSelection::Both).unwrap();
+
+        let first = check_examples(&jobs, "1");
+        let second = check_examples(&jobs, "spelled digits");
+
+        assert_eq!(first[0].verdict, Verdict::Pass);
+        assert_eq!(first[0].input.as_deref(), Some("example digits"));
+        assert_eq!(first[1].verdict, Verdict::Missing);
+        assert_eq!(second[0].verdict, Verdict::Missing);
+        assert_eq!(second[1].verdict, Verdict::Pass);
+        assert_eq!(second[1].input.as_deref(), Some("example spelled digits"));
+        assert_eq!(check_examples(&jobs, "3")[0].verdict, Verdict::Missing);
+        assert_eq!(
+            check_examples(&jobs, "nope")[0].input.as_deref(),
+            Some("example nope")
+        );
+    }
+
+    #[test]
+    fn test_checked_in_manifest_is_valid() {
+        let manifest = Manifest::load(DEFAULT_PATH).unwrap();
+        let registry = crate::registry();
+
+        for entry in manifest.entries.iter() {
+            let solver = registry.get(entry.day).unwrap();
+
+            assert!(solver.supports(entry.part));
+            assert!(std::path::Path::new(&entry.input).exists());
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// A global allocator that counts what each thread allocates, so a solve can
+/// be measured without other threads' allocations leaking into its numbers.
+///
+/// Nothing is counted unless it is installed:
+///
+/// ```
+/// use adventofcode2023::memory::{self, CountingAllocator};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+///
+/// fn main() {
+///     let (buffer, memory) = memory::measure(|| vec![0u8; 1024]);
+///
+///     assert_eq!(buffer.len(), 1024);
+///     assert_eq!((memory.allocations, memory.bytes, memory.peak), (1, 1024, 1024));
+/// }
+/// ```
+pub struct CountingAllocator;
+
+/// Running totals for the current thread. Live bytes can go negative when a
+/// thread frees memory another thread allocated.
+struct Counters {
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    // Const-initialized without a destructor, so using it never allocates.
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+fn record_alloc(size: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        let live = counters.live.get() + size as i64;
+
+        counters.allocations.set(counters.allocations.get() + 1);
+        counters.bytes.set(counters.bytes.get() + size as u64);
+        counters.live.set(live);
+        counters.peak.set(counters.peak.get().max(live));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let _ = COUNTERS.try_with(|counters| counters.live.set(counters.live.get() - size as i64));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+
+        // A reallocation counts as a new allocation of the new size that
+        // replaces the old one.
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// What the current thread allocated while a closure ran.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Memory {
+    /// The number of allocations, including reallocations.
+    pub allocations: u64,
+    /// The total number of bytes allocated, whether or not they were freed.
+    pub bytes: u64,
+    /// The most bytes that were allocated and not yet freed at any one time.
+    pub peak: u64,
+}
+
+/// Runs `f` and reports what it allocated on the current thread. Everything
+/// is zero unless a [`CountingAllocator`] is installed.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Memory) {
+    let (allocations, bytes, live, peak) = COUNTERS.with(|counters| {
+        let start = (
+            counters.allocations.get(),
+            counters.bytes.get(),
+            counters.live.get(),
+            counters.peak.get(),
+        );
+
+        counters.peak.set(start.2);
+        start
+    });
+
+    let result = f();
+
+    let memory = COUNTERS.with(|counters| {
+        let memory = Memory {
+            allocations: counters.allocations.get() - allocations,
+            bytes: counters.bytes.get() - bytes,
+            peak: (counters.peak.get() - live).max(0) as u64,
+        };
+
+        // Keep an enclosing measurement's peak intact.
+        counters.peak.set(counters.peak.get().max(peak));
+        memory
+    });
+
+    (result, memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        let (sum, memory) = measure(|| {
+            let first = vec![1u8; 4000];
+            let second = vec![2u8; 1000];
+            drop(first);
+            let third = vec![3u8; 2000];
+
+            second.iter().chain(&third).map(|&x| x as u64).sum::<u64>()
+        });
+
+        assert_eq!(sum, 8000);
+        assert_eq!(memory.allocations, 3);
+        assert_eq!(memory.bytes, 7000);
+        assert_eq!(memory.peak, 5000);
+    }
+
+    #[test]
+    fn test_measure_ignores_other_threads() {
+        let (_, memory) = measure(|| {
+            std::thread::scope(|scope| {
+                scope.spawn(|| vec![0u8; 1 << 20]).join().unwrap();
+            })
+        });
+
+        assert!(memory.bytes < 1 << 20);
+    }
+}
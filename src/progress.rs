@@ -0,0 +1,251 @@
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::error::{self, Error};
+
+/// The part of a day whose solver is running on the current thread.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Task {
+    pub day: u8,
+    pub part: u8,
+}
+
+/// Receives progress from long-running solvers. Updates can arrive from
+/// several threads at once and very often, so implementations should be
+/// cheap and do their own throttling.
+pub trait Reporter: Send + Sync {
+    /// `task` has finished `completed` of `total` units of work. The task is
+    /// unknown if the solver was run outside [`run_as`].
+    fn update(&self, task: Option<Task>, completed: u64, total: u64);
+
+    /// `task` has stopped running, whether or not it reported any progress.
+    fn finish(&self, _task: Task) {}
+}
+
+static REPORTER: OnceLock<Box<dyn Reporter>> = OnceLock::new();
+
+thread_local! {
+    static TASK: Cell<Option<Task>> = const { Cell::new(None) };
+    static CANCEL: RefCell<Option<Cancel>> = const { RefCell::new(None) };
+}
+
+/// Asks a solver running on another thread to stop. The solver notices the
+/// next time it reports progress.
+#[derive(Clone, Debug, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Installs the reporter that receives all progress for the rest of the
+/// process. Fails if one was already installed.
+pub fn set_reporter(reporter: impl Reporter + 'static) -> Result<(), String> {
+    REPORTER
+        .set(Box::new(reporter))
+        .map_err(|_| "a progress reporter is already installed".to_string())
+}
+
+/// Reports that `completed` of `total` units of work are done. Does nothing
+/// unless a reporter is installed, but solvers should still only call this
+/// every so many units rather than on every one.
+///
+/// Fails with [`Error::Cancelled`] once the run has been cancelled, which the
+/// solver should pass on so that it stops.
+pub fn report(completed: u64, total: u64) -> error::Result<()> {
+    if CANCEL.with(|cancel| cancel.borrow().as_ref().is_some_and(Cancel::is_cancelled)) {
+        return Err(Error::Cancelled);
+    }
+
+    if let Some(reporter) = REPORTER.get() {
+        reporter.update(TASK.with(Cell::get), completed, total);
+    }
+
+    Ok(())
+}
+
+/// Runs `f` so that progress reports on the current thread fail once
+/// `cancel` is cancelled.
+pub fn cancellable<R>(cancel: Cancel, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous cancellation even if `f` panics.
+    struct Guard(Option<Cancel>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            CANCEL.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _guard = Guard(CANCEL.with(|current| current.replace(Some(cancel))));
+
+    f()
+}
+
+/// Runs `f` with progress on the current thread attributed to `task`.
+pub fn run_as<R>(task: Task, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous task even if `f` panics.
+    struct Guard(Option<Task>, Task);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            TASK.with(|current| current.set(self.0));
+
+            if let Some(reporter) = REPORTER.get() {
+                reporter.finish(self.1);
+            }
+        }
+    }
+
+    let _guard = Guard(TASK.with(|current| current.replace(Some(task))), task);
+
+    f()
+}
+
+/// Lets something through at most once per interval.
+pub struct Throttle {
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Whether the interval has passed since the last time this returned
+    /// `true`. The first call always does.
+    pub fn ready(&self) -> bool {
+        let now = Instant::now();
+        let mut last = self.last.lock().unwrap();
+
+        match *last {
+            Some(last) if now - last < self.interval => false,
+            _ => {
+                *last = Some(now);
+                true
+            }
+        }
+    }
+}
+
+/// Describes progress for a person, like `Day 5 part 2: 41.3% (413/1000)`.
+pub fn describe(task: Option<Task>, completed: u64, total: u64) -> String {
+    let percent = if total == 0 {
+        100.0
+    } else {
+        completed as f64 / total as f64 * 100.0
+    };
+
+    let task = task.map_or_else(String::new, |task| {
+        format!("Day {} part {}: ", task.day, task.part)
+    });
+
+    format!("{}{:.1}% ({}/{})", task, percent, completed, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static RECORDED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Recorder;
+
+    impl Reporter for Recorder {
+        fn update(&self, task: Option<Task>, completed: u64, total: u64) {
+            RECORDED
+                .lock()
+                .unwrap()
+                .push(describe(task, completed, total));
+        }
+
+        fn finish(&self, task: Task) {
+            RECORDED
+                .lock()
+                .unwrap()
+                .push(format!("Day {} part {}: done", task.day, task.part));
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        set_reporter(Recorder).unwrap();
+
+        run_as(Task { day: 99, part: 2 }, || {
+            report(1, 4).unwrap();
+            report(4, 4).unwrap();
+        });
+
+        assert!(set_reporter(Recorder).is_err());
+
+        // Solvers run by other tests report too, so only look at this task.
+        let recorded = RECORDED
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|line| line.starts_with("Day 99 "))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            recorded,
+            [
+                "Day 99 part 2: 25.0% (1/4)",
+                "Day 99 part 2: 100.0% (4/4)",
+                "Day 99 part 2: done"
+            ]
+        );
+        assert_eq!(TASK.with(Cell::get), None);
+    }
+
+    #[test]
+    fn test_cancellable() {
+        let cancel = Cancel::new();
+
+        cancellable(cancel.clone(), || {
+            assert_eq!(report(1, 2), Ok(()));
+
+            cancel.cancel();
+
+            assert_eq!(report(2, 2), Err(Error::Cancelled));
+        });
+
+        assert_eq!(report(2, 2), Ok(()));
+    }
+
+    #[test]
+    fn test_throttle() {
+        let throttle = Throttle::new(Duration::from_millis(50));
+
+        assert!(throttle.ready());
+        assert!(!throttle.ready());
+
+        std::thread::sleep(Duration::from_millis(60));
+
+        assert!(throttle.ready());
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(Some(Task { day: 5, part: 2 }), 413, 1000),
+            "Day 5 part 2: 41.3% (413/1000)"
+        );
+        assert_eq!(describe(None, 0, 0), "100.0% (0/0)");
+    }
+}
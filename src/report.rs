@@ -0,0 +1,278 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::answer::Answer;
+use crate::runner::{Outcome, Run};
+
+/// How the results of a run are printed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// Readable messages and tables.
+    Text,
+    /// A JSON array with one object per run.
+    Json,
+    /// A header row followed by one row per run.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("expected `text`, `json` or `csv`, got `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+            Self::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// One run as a machine-readable record. Times are in nanoseconds, memory is
+/// in bytes, and a failed or skipped run has an error or the reason it was
+/// skipped instead of an answer and measurements.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub answer: Option<Answer>,
+    pub parse_ns: Option<u128>,
+    pub solve_ns: Option<u128>,
+    pub error: Option<String>,
+    pub skipped: Option<String>,
+    #[cfg(feature = "alloc-metrics")]
+    pub allocations: Option<u64>,
+    #[cfg(feature = "alloc-metrics")]
+    pub allocated_bytes: Option<u64>,
+    #[cfg(feature = "alloc-metrics")]
+    pub peak_bytes: Option<u64>,
+}
+
+impl From<&Run> for Record {
+    fn from(run: &Run) -> Self {
+        let (answer, timings, error, skipped) = match &run.outcome {
+            Outcome::Solved {
+                answer, timings, ..
+            } => (Some(answer.clone()), Some(timings), None, None),
+            Outcome::Failed(message) => (None, None, Some(message.clone()), None),
+            Outcome::Skipped(reason) => (None, None, None, Some(reason.clone())),
+        };
+
+        #[cfg(feature = "alloc-metrics")]
+        let memory = match &run.outcome {
+            Outcome::Solved { memory, .. } => Some(memory),
+            _ => None,
+        };
+
+        Self {
+            day: run.day,
+            part: run.part,
+            input: run.input.as_ref().map(ToString::to_string),
+            answer,
+            parse_ns: timings.map(|timings| timings.parse.as_nanos()),
+            solve_ns: timings.map(|timings| timings.solve.as_nanos()),
+            error,
+            skipped,
+            #[cfg(feature = "alloc-metrics")]
+            allocations: memory.map(|memory| memory.allocations),
+            #[cfg(feature = "alloc-metrics")]
+            allocated_bytes: memory.map(|memory| memory.bytes),
+            #[cfg(feature = "alloc-metrics")]
+            peak_bytes: memory.map(|memory| memory.peak),
+        }
+    }
+}
+
+/// Renders the runs as a JSON array of records.
+pub fn json(runs: &[Run]) -> String {
+    let records = runs.iter().map(Record::from).collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&records).expect("records are always serializable")
+}
+
+/// Renders the runs as CSV with a header row. Missing values are left empty.
+pub fn csv(runs: &[Run]) -> String {
+    let mut csv = String::from("day,part,input,answer,parse_ns,solve_ns,error,skipped");
+    #[cfg(feature = "alloc-metrics")]
+    csv.push_str(",allocations,allocated_bytes,peak_bytes");
+    csv.push('\n');
+
+    for record in runs.iter().map(Record::from) {
+        let fields = [
+            record.day.to_string(),
+            record.part.to_string(),
+            record.input.unwrap_or_default(),
+            record
+                .answer
+                .map(|answer| answer.to_string())
+                .unwrap_or_default(),
+            record.parse_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+            record.solve_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+            record.error.unwrap_or_default(),
+            record.skipped.unwrap_or_default(),
+            #[cfg(feature = "alloc-metrics")]
+            record
+                .allocations
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            #[cfg(feature = "alloc-metrics")]
+            record
+                .allocated_bytes
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            #[cfg(feature = "alloc-metrics")]
+            record.peak_bytes.map(|n| n.to_string()).unwrap_or_default(),
+        ];
+
+        csv.push_str(&fields.map(|field| escape(&field)).join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quotes a CSV field if it contains anything that would break the row.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Memory;
+    use crate::runner::Source;
+    use crate::solver::Timings;
+    use std::time::Duration;
+
+    fn runs() -> Vec<Run> {
+        vec![
+            Run {
+                day: 6,
+                part: 1,
+                input: Some(Source::File("inputs/06.txt".to_string())),
+                outcome: Outcome::Solved {
+                    answer: Answer::from(288i64),
+                    timings: Timings {
+                        parse: Duration::from_nanos(1500),
+                        solve: Duration::from_nanos(250),
+                    },
+                    memory: Memory {
+                        allocations: 3,
+                        bytes: 96,
+                        peak: 64,
+                    },
+                },
+            },
+            Run {
+                day: 8,
+                part: 1,
+                input: Some(Source::Stdin),
+                outcome: Outcome::Failed("missing data: no node \"BBB\", sorry".to_string()),
+            },
+            Run {
+                day: 10,
+                part: 2,
+                input: Some(Source::File("inputs/10.txt".to_string())),
+                outcome: Outcome::Skipped("no input".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("JSON".parse(), Ok(Format::Json));
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-metrics"))]
+    fn test_json() {
+        let json: serde_json::Value = serde_json::from_str(&json(&runs())).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "day": 6,
+                    "part": 1,
+                    "input": "inputs/06.txt",
+                    "answer": 288,
+                    "parse_ns": 1500,
+                    "solve_ns": 250,
+                    "error": null,
+                    "skipped": null
+                },
+                {
+                    "day": 8,
+                    "part": 1,
+                    "input": "stdin",
+                    "answer": null,
+                    "parse_ns": null,
+                    "solve_ns": null,
+                    "error": "missing data: no node \"BBB\", sorry",
+                    "skipped": null
+                },
+                {
+                    "day": 10,
+                    "part": 2,
+                    "input": "inputs/10.txt",
+                    "answer": null,
+                    "parse_ns": null,
+                    "solve_ns": null,
+                    "error": null,
+                    "skipped": "no input"
+                }
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-metrics"))]
+    fn test_csv() {
+        assert_eq!(
+            csv(&runs()),
+            "day,part,input,answer,parse_ns,solve_ns,error,skipped\n\
+             6,1,inputs/06.txt,288,1500,250,,\n\
+             8,1,stdin,,,,\"missing data: no node \"\"BBB\"\", sorry\",\n\
+             10,2,inputs/10.txt,,,,,no input\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-metrics")]
+    fn test_memory() {
+        let records = runs().iter().map(Record::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            (
+                records[0].allocations,
+                records[0].allocated_bytes,
+                records[0].peak_bytes
+            ),
+            (Some(3), Some(96), Some(64))
+        );
+        assert_eq!(records[1].allocations, None);
+        assert!(csv(&runs()).starts_with(
+            "day,part,input,answer,parse_ns,solve_ns,error,skipped,allocations,allocated_bytes,peak_bytes\n\
+             6,1,inputs/06.txt,288,1500,250,,,3,96,64\n"
+        ));
+    }
+}
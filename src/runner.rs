@@ -0,0 +1,722 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::memory::Memory;
+use crate::progress::{self, Cancel, Task};
+use crate::solver::{Registry, Solution, Solver, Timings};
+use crate::utils;
+
+/// Which days to solve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DaySelection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Self::All);
+        }
+
+        s.parse()
+            .map(Self::Day)
+            .map_err(|_| format!("expected a day number or `all`, got `{}`", s))
+    }
+}
+
+impl fmt::Display for DaySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Day(day) => write!(f, "{}", day),
+        }
+    }
+}
+
+/// Which parts of each selected day to solve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartSelection {
+    Both,
+    Part(u8),
+}
+
+impl FromStr for PartSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("both") {
+            return Ok(Self::Both);
+        }
+
+        s.parse()
+            .map(Self::Part)
+            .map_err(|_| format!("expected a part number or `both`, got `{}`", s))
+    }
+}
+
+impl fmt::Display for PartSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Both => write!(f, "both"),
+            Self::Part(part) => write!(f, "{}", part),
+        }
+    }
+}
+
+/// A single part of a single day that should be solved.
+#[derive(Clone, Copy)]
+pub struct Job {
+    pub solver: &'static dyn Solver,
+    pub part: u8,
+}
+
+/// Resolves a day and part selection against the registry.
+///
+/// Selecting a single day requires that day (and the part, unless `both` was
+/// given) to exist. Selecting all days skips days that lack the part.
+pub fn select(
+    registry: &Registry,
+    days: DaySelection,
+    parts: PartSelection,
+) -> Result<Vec<Job>, String> {
+    let solvers = match days {
+        DaySelection::All => registry.iter().collect::<Vec<_>>(),
+        DaySelection::Day(day) => match registry.get(day) {
+            Some(solver) => vec![solver],
+            None => return Err(format!("Invalid day number: {}", day)),
+        },
+    };
+
+    if let (DaySelection::Day(_), PartSelection::Part(part)) = (days, parts) {
+        if !solvers[0].supports(part) {
+            return Err(format!("Invalid part number: {}", part));
+        }
+    }
+
+    Ok(solvers
+        .into_iter()
+        .flat_map(|solver| {
+            solver
+                .parts()
+                .iter()
+                .filter(move |&&part| {
+                    parts == PartSelection::Both || parts == PartSelection::Part(part)
+                })
+                .map(move |&part| Job { solver, part })
+        })
+        .collect())
+}
+
+/// The default input file for a day.
+pub fn input_path(day: u8) -> String {
+    format!("inputs/{:02}.txt", day)
+}
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(String),
+}
+
+impl Source {
+    /// Interprets an `--file` argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Self::Stdin,
+            path => Self::File(path.to_string()),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        let result = match self {
+            Self::Stdin => std::io::read_to_string(std::io::stdin()),
+            Self::File(path) => std::fs::read_to_string(path),
+        };
+
+        result.map_err(|e| format!("could not read {}: {}", self, e))
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "stdin"),
+            Self::File(path) => write!(f, "{}", path),
+        }
+    }
+}
+
+/// Expands `--file` arguments into the inputs they name. Directories stand
+/// for every file directly inside them, in name order.
+pub fn sources(args: &[String]) -> Result<Vec<Source>, String> {
+    let mut sources = Vec::new();
+
+    for arg in args {
+        let path = Path::new(arg);
+
+        if arg == "-" && sources.contains(&Source::Stdin) {
+            return Err("stdin can only be given once".to_string());
+        } else if !path.is_dir() {
+            sources.push(Source::from_arg(arg));
+            continue;
+        }
+
+        let mut files = std::fs::read_dir(path)
+            .map_err(|e| format!("could not read {}: {}", arg, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+
+        if files.is_empty() {
+            return Err(format!("no input files in {}", arg));
+        }
+
+        files.sort();
+        sources.extend(
+            files
+                .into_iter()
+                .map(|file| Source::File(file.to_string_lossy().into_owned())),
+        );
+    }
+
+    Ok(sources)
+}
+
+/// Solves every job against every source, reading each source only once,
+/// on up to `threads` threads at once. The runs are ordered by source, then
+/// by job, and any run that takes longer than `timeout` is reported as timed
+/// out.
+///
+/// Solvers running at once compete for the CPU, so their timings are only
+/// accurate with a single thread.
+pub fn run_sources(
+    jobs: &[Job],
+    sources: &[Source],
+    threads: usize,
+    timeout: Option<Duration>,
+) -> Vec<Run> {
+    let inputs = sources.iter().map(Source::read).collect::<Vec<_>>();
+    let runs = sources
+        .iter()
+        .zip(&inputs)
+        .flat_map(|(source, input)| jobs.iter().map(move |job| (job, source, input)))
+        .collect::<Vec<_>>();
+
+    map_parallel(&runs, threads, |&(job, source, input)| {
+        let run = match input {
+            Ok(input) => job.run_with_timeout(input, timeout),
+            Err(message) => job.finish(Outcome::Failed(message.clone())),
+        };
+
+        Run {
+            input: Some(source.clone()),
+            ..run
+        }
+    })
+}
+
+/// Solves every job against its default input, on up to `threads` threads at
+/// once. The runs come back in the order of the jobs, and as with
+/// [`run_sources`], timings are only accurate with a single thread.
+pub fn run_all(jobs: &[Job], threads: usize, timeout: Option<Duration>) -> Vec<Run> {
+    map_parallel(jobs, threads, |job| job.run_default_input(timeout))
+}
+
+/// Runs `f` on a worker thread and waits at most `timeout` for it, returning
+/// how long was waited if it did not finish in time.
+///
+/// When `f` times out its progress reports are cancelled, so a solver that
+/// reports progress stops soon after. One that never reports keeps running
+/// in the background until the process exits. A panic in `f` is resumed on
+/// the calling thread.
+pub fn with_timeout<R: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, Duration> {
+    let (sender, receiver) = mpsc::channel();
+    let cancel = Cancel::new();
+    let start = Instant::now();
+
+    let worker = thread::spawn({
+        let cancel = cancel.clone();
+
+        move || {
+            // The receiver is gone if the caller already gave up.
+            let _ = sender.send(progress::cancellable(cancel, f));
+        }
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            Err(start.elapsed())
+        }
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("the worker sends a result before it finishes"),
+        },
+    }
+}
+
+/// Applies `f` to every item on a pool of at most `threads` threads, and
+/// returns the results in the order of the items. With a single thread the
+/// items are processed in turn on the calling thread.
+pub fn map_parallel<T: Sync, R: Send>(
+    items: &[T],
+    threads: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let threads = threads.clamp(1, items.len().max(1));
+
+    if threads == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                // Each worker takes the next unclaimed item until none are left.
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+
+                    let result = f(item);
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+/// What happened when a job was run.
+#[derive(Debug)]
+pub enum Outcome {
+    Solved {
+        answer: Answer,
+        timings: Timings,
+        memory: Memory,
+    },
+    Failed(String),
+    /// The job was not run, for a reason that is not the solver's fault.
+    Skipped(String),
+}
+
+impl Outcome {
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Self::Solved { .. })
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+/// The answer, the error the job failed with, or why it was skipped.
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solved { answer, .. } => write!(f, "{}", answer),
+            Self::Failed(message) | Self::Skipped(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// A job together with its outcome.
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    /// Where the input came from, if the job was not given the text directly.
+    pub input: Option<Source>,
+    pub outcome: Outcome,
+}
+
+impl Job {
+    pub fn day(&self) -> u8 {
+        self.solver.day()
+    }
+
+    /// Solves the job against the given input, catching any panic raised by
+    /// the solver so one broken day cannot take down the rest of a run.
+    pub fn run(&self, input: &str) -> Run {
+        let task = Task {
+            day: self.day(),
+            part: self.part,
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            progress::run_as(task, || self.solver.run(self.part, input))
+        }));
+
+        let outcome = match result {
+            Ok(Ok(Solution {
+                answer,
+                timings,
+                memory,
+            })) => Outcome::Solved {
+                answer,
+                timings,
+                memory,
+            },
+            Ok(Err(e)) => Outcome::Failed(e.to_string()),
+            Err(payload) => Outcome::Failed(format!("panicked: {}", panic_message(&payload))),
+        };
+
+        self.finish(outcome)
+    }
+
+    /// Like [`Job::run`], but gives up on the solver if it has not finished
+    /// within `timeout`.
+    pub fn run_with_timeout(&self, input: &str, timeout: Option<Duration>) -> Run {
+        let Some(timeout) = timeout else {
+            return self.run(input);
+        };
+
+        let (job, input) = (*self, input.to_string());
+
+        with_timeout(timeout, move || job.run(&input)).unwrap_or_else(|elapsed| {
+            self.finish(Outcome::Failed(format!(
+                "timed out after {}",
+                utils::format_duration(elapsed)
+            )))
+        })
+    }
+
+    /// The default input file for the job's day, or `None` if it has not
+    /// been downloaded yet.
+    pub fn default_input(&self) -> Option<Source> {
+        let path = input_path(self.day());
+
+        Path::new(&path).exists().then_some(Source::File(path))
+    }
+
+    /// Reads the default input file for the job's day and solves the job.
+    /// The job is skipped if the day has no input file yet.
+    pub fn run_default_input(&self, timeout: Option<Duration>) -> Run {
+        let Some(source) = self.default_input() else {
+            return Run {
+                input: Some(Source::File(input_path(self.day()))),
+                ..self.finish(Outcome::Skipped("no input".to_string()))
+            };
+        };
+
+        run_sources(std::slice::from_ref(self), &[source], 1, timeout).remove(0)
+    }
+
+    fn finish(&self, outcome: Outcome) -> Run {
+        Run {
+            day: self.day(),
+            part: self.part,
+            input: None,
+            outcome,
+        }
+    }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    struct Fake;
+
+    impl Solver for Fake {
+        fn day(&self) -> u8 {
+            3
+        }
+
+        fn parts(&self) -> &'static [u8] {
+            &[1, 2]
+        }
+
+        fn run(&self, part: u8, input: &str) -> Result<Solution> {
+            match part {
+                1 => Solution::measure(input, |input| Ok(input.len()), |len| Ok(Answer::from(len))),
+                _ => panic!("no part {} yet", part),
+            }
+        }
+    }
+
+    /// A day whose input will never be downloaded.
+    struct NoInput;
+
+    impl Solver for NoInput {
+        fn day(&self) -> u8 {
+            26
+        }
+
+        fn parts(&self) -> &'static [u8] {
+            &[1]
+        }
+
+        fn run(&self, _part: u8, _input: &str) -> Result<Solution> {
+            panic!("there is no input to run on")
+        }
+    }
+
+    #[test]
+    fn test_selection_parsing() {
+        assert_eq!("all".parse(), Ok(DaySelection::All));
+        assert_eq!("19".parse(), Ok(DaySelection::Day(19)));
+        assert!("nineteen".parse::<DaySelection>().is_err());
+
+        assert_eq!("both".parse(), Ok(PartSelection::Both));
+        assert_eq!("2".parse(), Ok(PartSelection::Part(2)));
+        assert!("".parse::<PartSelection>().is_err());
+    }
+
+    #[test]
+    fn test_select_all_skips_missing_parts() {
+        let registry = crate::registry();
+
+        let jobs = select(&registry, DaySelection::All, PartSelection::Part(2)).unwrap();
+
+        assert!(jobs.iter().all(|job| job.part == 2));
+        assert!(jobs.iter().all(|job| job.solver.supports(2)));
+
+        let jobs = select(&registry, DaySelection::All, PartSelection::Both).unwrap();
+        let total = registry
+            .iter()
+            .map(|solver| solver.parts().len())
+            .sum::<usize>();
+
+        assert_eq!(jobs.len(), total);
+    }
+
+    #[test]
+    fn test_select_single_day() {
+        let registry = crate::registry();
+
+        let jobs = select(&registry, DaySelection::Day(19), PartSelection::Both).unwrap();
+
+        assert_eq!(jobs.len(), 2);
+        assert!(select(&registry, DaySelection::Day(99), PartSelection::Both).is_err());
+        assert!(select(&registry, DaySelection::Day(19), PartSelection::Part(3)).is_err());
+    }
+
+    #[test]
+    fn test_sources() {
+        let args = ["-", "inputs", "missing.txt"].map(String::from);
+        let sources = sources(&args).unwrap();
+
+        assert_eq!(sources[0], Source::Stdin);
+        assert!(sources.contains(&Source::File("inputs/06.txt".to_string())));
+        assert_eq!(
+            sources.last(),
+            Some(&Source::File("missing.txt".to_string()))
+        );
+
+        let files = &sources[1..sources.len() - 1];
+        assert!(files
+            .windows(2)
+            .all(|pair| pair[0].to_string() < pair[1].to_string()));
+
+        assert!(super::sources(&["-", "-"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn test_run_sources() {
+        let jobs = [Job {
+            solver: &Fake,
+            part: 1,
+        }];
+        let sources = [
+            Source::File("inputs/06.txt".to_string()),
+            Source::File("missing.txt".to_string()),
+        ];
+
+        let runs = run_sources(&jobs, &sources, 2, None);
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].input, Some(sources[0].clone()));
+        assert!(runs[0].outcome.is_solved());
+        assert!(
+            matches!(&runs[1].outcome, Outcome::Failed(message) if message.starts_with("could not read missing.txt"))
+        );
+    }
+
+    #[test]
+    fn test_run_all_skips_days_without_input() {
+        let mut registry = Registry::new();
+        registry.register(crate::registry().get(6).unwrap());
+        registry.register(&NoInput);
+        let jobs = select(&registry, DaySelection::All, PartSelection::Part(1)).unwrap();
+
+        let runs = run_all(&jobs, 2, None);
+
+        assert!(runs[0].outcome.is_solved());
+        assert_eq!(
+            runs[1].input,
+            Some(Source::File("inputs/26.txt".to_string()))
+        );
+        assert!(matches!(&runs[1].outcome, Outcome::Skipped(reason) if reason == "no input"));
+        assert!(!runs.iter().any(|run| run.outcome.is_failed()));
+    }
+
+    #[test]
+    fn test_run_catches_panics() {
+        let solved = Job {
+            solver: &Fake,
+            part: 1,
+        }
+        .run("abc");
+        let panicked = Job {
+            solver: &Fake,
+            part: 2,
+        }
+        .run("abc");
+
+        assert!(matches!(solved.outcome, Outcome::Solved { answer, .. } if answer == 3));
+        assert!(
+            matches!(panicked.outcome, Outcome::Failed(message) if message.contains("no part 2 yet"))
+        );
+    }
+
+    #[test]
+    fn test_map_parallel_keeps_order() {
+        let items = (0..20u64).collect::<Vec<_>>();
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Mutex::new(receiver);
+
+        let results = map_parallel(&items, 4, |&item| {
+            // The first item cannot finish before the last one has.
+            match item {
+                0 => receiver
+                    .lock()
+                    .unwrap()
+                    .recv_timeout(Duration::from_secs(10))
+                    .unwrap(),
+                19 => sender.send(()).unwrap(),
+                _ => {}
+            }
+
+            item * 2
+        });
+
+        assert_eq!(results, (0..40).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_map_parallel_single_thread() {
+        let caller = std::thread::current().id();
+        let results = map_parallel(&[1, 2, 3], 1, |_| std::thread::current().id());
+
+        assert!(results.iter().all(|&thread| thread == caller));
+        assert!(map_parallel(&[] as &[u8], 8, |&x| x).is_empty());
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        struct Slow;
+
+        impl Solver for Slow {
+            fn day(&self) -> u8 {
+                5
+            }
+
+            fn parts(&self) -> &'static [u8] {
+                &[1]
+            }
+
+            fn run(&self, _: u8, input: &str) -> Result<Solution> {
+                Solution::measure(
+                    input,
+                    |input| {
+                        std::thread::sleep(Duration::from_millis(input.parse().unwrap()));
+                        Ok(())
+                    },
+                    |_| Ok(Answer::from(1i64)),
+                )
+            }
+        }
+
+        let job = Job {
+            solver: &Slow,
+            part: 1,
+        };
+
+        let quick = job.run_with_timeout("1", Some(Duration::from_secs(5)));
+        let slow = job.run_with_timeout("2000", Some(Duration::from_millis(50)));
+
+        assert!(quick.outcome.is_solved());
+        assert!(
+            matches!(slow.outcome, Outcome::Failed(message) if message.starts_with("timed out after "))
+        );
+
+        let elapsed = with_timeout(Duration::from_millis(50), || {
+            std::thread::sleep(Duration::from_secs(2));
+        })
+        .unwrap_err();
+
+        assert!(elapsed >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_with_timeout_cancels_the_worker() {
+        let (sender, receiver) = mpsc::channel();
+
+        let result = with_timeout(Duration::from_millis(50), move || {
+            // Work until told to stop, like a brute-force solver.
+            let mut done = 0;
+
+            let error = loop {
+                done += 1;
+                std::thread::sleep(Duration::from_millis(1));
+
+                if let Err(error) = progress::report(done, u64::MAX) {
+                    break error;
+                }
+            };
+
+            sender.send(error).unwrap();
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(5)),
+            Ok(crate::error::Error::Cancelled)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn test_with_timeout_resumes_panics() {
+        let _ = with_timeout(Duration::from_secs(5), || panic!("boom"));
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const GRAMMAR_DAY_TEMPLATE: &str = include_str!("../templates/day_grammar.rs.tmpl");
+const AST_TEMPLATE: &str = include_str!("../templates/ast.rs.tmpl");
+const PARSER_TEMPLATE: &str = include_str!("../templates/parser.lalrpop.tmpl");
+
+/// Generates the module for a new day under `root/src` and registers it in
+/// `lib.rs`, returning the files that were created.
+///
+/// With `grammar`, the day also gets an `ast.rs` and a `parser.lalrpop` like
+/// the days parsed with LALRPOP. Nothing is written if the day already exists
+/// or `lib.rs` cannot be updated.
+pub fn generate(root: &Path, day: u8, grammar: bool) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {} is not between 1 and 25", day));
+    }
+
+    let src = root.join("src");
+    let module = format!("day_{:02}", day);
+    let file = src.join(format!("{}.rs", module));
+    let dir = src.join(&module);
+
+    if let Some(existing) = [&file, &dir].into_iter().find(|path| path.exists()) {
+        return Err(format!("{} already exists", existing.display()));
+    }
+
+    let lib_path = src.join("lib.rs");
+    let lib = std::fs::read_to_string(&lib_path)
+        .map_err(|e| format!("could not read {}: {}", lib_path.display(), e))?;
+    let lib = register(&lib, day)?;
+
+    let mut files = vec![(
+        file,
+        if grammar {
+            GRAMMAR_DAY_TEMPLATE
+        } else {
+            DAY_TEMPLATE
+        },
+    )];
+
+    if grammar {
+        files.push((dir.join("ast.rs"), AST_TEMPLATE));
+        files.push((dir.join("parser.lalrpop"), PARSER_TEMPLATE));
+        std::fs::create_dir(&dir)
+            .map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+    }
+
+    for (path, template) in &files {
+        std::fs::write(path, render(template, day))
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+    }
+
+    std::fs::write(&lib_path, lib)
+        .map_err(|e| format!("could not write {}: {}", lib_path.display(), e))?;
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Fills in the day number, as written in code and zero-padded in names.
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{NN}}", &format!("{:02}", day))
+        .replace("{{DAY}}", &day.to_string())
+}
+
+/// Adds the day's `pub mod` and its `registry.register` call to the contents
+/// of `lib.rs`, keeping both lists in order.
+fn register(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("day_{:02}", day);
+    let declaration = format!("pub mod {};", module);
+    let registration = format!("    registry.register(&{}::Day{:02});", module, day);
+
+    if lib.lines().any(|line| line == declaration) {
+        return Err(format!("{} is already declared in lib.rs", module));
+    }
+
+    let mut lines = lib.lines().collect::<Vec<_>>();
+
+    insert_sorted(&mut lines, &declaration, |line| {
+        line.starts_with("pub mod ")
+    })
+    .ok_or("could not find the module declarations in lib.rs")?;
+    insert_sorted(&mut lines, &registration, |line| {
+        line.starts_with("    registry.register(")
+    })
+    .ok_or("could not find the registry in lib.rs")?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Inserts `line` among the run of lines matching `group`, before the first
+/// that sorts after it. Returns `None` if no line matches.
+fn insert_sorted<'a>(
+    lines: &mut Vec<&'a str>,
+    line: &'a str,
+    group: impl Fn(&str) -> bool,
+) -> Option<()> {
+    let first = lines.iter().position(|l| group(l))?;
+    let end = lines[first..]
+        .iter()
+        .position(|l| !group(l))
+        .map_or(lines.len(), |i| first + i);
+    let at = lines[first..end]
+        .iter()
+        .position(|l| *l > line)
+        .map_or(end, |i| first + i);
+
+    lines.insert(at, line);
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod answer;
+pub mod day_01;
+pub mod day_04;
+pub mod error;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register(&day_01::Day01);
+    registry.register(&day_04::Day04);
+
+    registry
+}
+";
+
+    fn scratch(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), LIB).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn test_register() {
+        let lib = register(LIB, 3).unwrap();
+
+        assert!(lib.contains("pub mod day_01;\npub mod day_03;\npub mod day_04;\n"));
+        assert!(lib.contains(
+            "    registry.register(&day_01::Day01);\n    registry.register(&day_03::Day03);\n    registry.register(&day_04::Day04);\n"
+        ));
+
+        let lib = register(LIB, 25).unwrap();
+
+        assert!(lib.contains("pub mod day_04;\npub mod day_25;\npub mod error;\n"));
+        assert!(lib.contains("    registry.register(&day_25::Day25);\n\n    registry\n"));
+    }
+
+    #[test]
+    fn test_generate() {
+        let root = scratch("plain");
+
+        assert_eq!(
+            generate(&root, 7, false),
+            Ok(vec![root.join("src/day_07.rs")])
+        );
+
+        let day = std::fs::read_to_string(root.join("src/day_07.rs")).unwrap();
+
+        assert!(day.contains("pub struct Day07;"));
+        assert!(day.contains("_ => Err(Error::UnsupportedPart { day: 7, part }),"));
+        assert!(day.contains("const EXAMPLES: &[Example] = &[];"));
+        assert!(day.contains("fn input() -> &'static str {"));
+        assert!(!day.contains("{{"));
+    }
+
+    #[test]
+    fn test_generate_grammar() {
+        let root = scratch("grammar");
+
+        assert_eq!(
+            generate(&root, 12, true),
+            Ok(vec![
+                root.join("src/day_12.rs"),
+                root.join("src/day_12/ast.rs"),
+                root.join("src/day_12/parser.lalrpop"),
+            ])
+        );
+
+        let day = std::fs::read_to_string(root.join("src/day_12.rs")).unwrap();
+        let grammar = std::fs::read_to_string(root.join("src/day_12/parser.lalrpop")).unwrap();
+
+        assert!(day.contains("\"/day_12/parser.rs\""));
+        assert!(day.contains("fn input() -> &'static str {"));
+        assert!(grammar.contains("use crate::day_12::ast::*;"));
+    }
+
+    #[test]
+    fn test_refuses_to_overwrite() {
+        let root = scratch("existing");
+        std::fs::write(root.join("src/day_04.rs"), "// solved").unwrap();
+
+        assert!(generate(&root, 4, false)
+            .unwrap_err()
+            .contains("already exists"));
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/day_04.rs")).unwrap(),
+            "// solved"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            LIB
+        );
+        assert_eq!(
+            generate(&root, 1, false),
+            Err("day_01 is already declared in lib.rs".to_string())
+        );
+        assert!(generate(&root, 26, false).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+use std::time::{Duration, Instant};
+
+use crate::answer::{Answer, StaticAnswer};
+use crate::error::Result;
+use crate::memory::{self, Memory};
+
+/// A solution to a single day of Advent of Code.
+///
+/// Implementors are registered into a [`Registry`] so that the CLI (or any
+/// other embedding code) can discover which days and parts are available
+/// without hard-coding them.
+pub trait Solver: Sync {
+    /// The day of the puzzle this solver is for.
+    fn day(&self) -> u8;
+
+    /// The parts of the puzzle this solver can solve.
+    fn parts(&self) -> &'static [u8];
+
+    /// Parses the input and solves the given part of the puzzle, timing each
+    /// phase separately.
+    ///
+    /// Asking for a part that is not listed in [`Solver::parts`] returns
+    /// [`Error::UnsupportedPart`](crate::error::Error::UnsupportedPart).
+    fn run(&self, part: u8, input: &str) -> Result<Solution>;
+
+    /// Solves the given part of the puzzle for the given input.
+    fn solve(&self, part: u8, input: &str) -> Result<Answer> {
+        self.run(part, input).map(|solution| solution.answer)
+    }
+
+    /// Whether this solver can solve the given part.
+    fn supports(&self, part: u8) -> bool {
+        self.parts().contains(&part)
+    }
+
+    /// The worked examples from the puzzle description, in the order they
+    /// appear there.
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+}
+
+/// A worked example from a puzzle description.
+#[derive(Debug)]
+pub struct Example {
+    /// A short name for the example, unique within its day.
+    pub name: &'static str,
+    pub input: &'static str,
+    /// The answer the example gives for each part it covers, as
+    /// `(part, answer)` pairs.
+    pub answers: &'static [(u8, StaticAnswer)],
+}
+
+impl Example {
+    /// The answer the example gives for a part, if it covers that part.
+    pub fn answer(&self, part: u8) -> Option<Answer> {
+        self.answers
+            .iter()
+            .find(|(covered, _)| *covered == part)
+            .map(|&(_, answer)| answer.into())
+    }
+
+    /// Finds an example by its name, or by its number counting from 1.
+    pub fn find<'a>(examples: &'a [Example], selector: &str) -> Option<&'a Example> {
+        examples
+            .iter()
+            .find(|example| example.name == selector)
+            .or_else(|| {
+                let number = selector.parse::<usize>().ok()?;
+
+                examples.get(number.checked_sub(1)?)
+            })
+    }
+}
+
+/// How long each phase of a solve took.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// The answer to a part of a puzzle along with how long it took to find and
+/// how much memory finding it took.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub answer: Answer,
+    pub timings: Timings,
+    pub memory: Memory,
+}
+
+impl Solution {
+    /// Runs `parse` over the input and then `solve` over its output, timing
+    /// both phases with a monotonic clock and counting what they allocate.
+    pub fn measure<'a, P>(
+        input: &'a str,
+        parse: impl FnOnce(&'a str) -> Result<P>,
+        solve: impl FnOnce(P) -> Result<Answer>,
+    ) -> Result<Self> {
+        let (result, memory) = memory::measure(|| {
+            let start = Instant::now();
+            let parsed = parse(input)?;
+            let parsed_at = Instant::now();
+            let answer = solve(parsed)?;
+            let solved_at = Instant::now();
+
+            Ok((
+                answer,
+                Timings {
+                    parse: parsed_at - start,
+                    solve: solved_at - parsed_at,
+                },
+            ))
+        });
+
+        result.map(|(answer, timings)| Self {
+            answer,
+            timings,
+            memory,
+        })
+    }
+}
+
+/// A collection of solvers, kept ordered by day.
+#[derive(Default)]
+pub struct Registry {
+    solvers: Vec<&'static dyn Solver>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a solver to the registry, replacing any solver already
+    /// registered for the same day.
+    pub fn register(&mut self, solver: &'static dyn Solver) {
+        match self
+            .solvers
+            .binary_search_by_key(&solver.day(), |existing| existing.day())
+        {
+            Ok(index) => self.solvers[index] = solver,
+            Err(index) => self.solvers.insert(index, solver),
+        }
+    }
+
+    /// Gets the solver registered for the given day, if any.
+    pub fn get(&self, day: u8) -> Option<&'static dyn Solver> {
+        self.solvers
+            .binary_search_by_key(&day, |solver| solver.day())
+            .ok()
+            .map(|index| self.solvers[index])
+    }
+
+    /// Iterates over the registered solvers in day order.
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Solver> + '_ {
+        self.solvers.iter().copied()
+    }
+
+    /// The days that have a registered solver, in order.
+    pub fn days(&self) -> Vec<u8> {
+        self.iter().map(|solver| solver.day()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake(u8);
+
+    impl Solver for Fake {
+        fn day(&self) -> u8 {
+            self.0
+        }
+
+        fn parts(&self) -> &'static [u8] {
+            &[1]
+        }
+
+        fn run(&self, _part: u8, input: &str) -> Result<Solution> {
+            Solution::measure(
+                input,
+                |input| Ok(input.len()),
+                |_| Ok(Answer::from(self.0 as i64)),
+            )
+        }
+    }
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        let mut registry = Registry::new();
+
+        registry.register(&Fake(9));
+        registry.register(&Fake(2));
+        registry.register(&Fake(5));
+
+        assert_eq!(registry.days(), vec![2, 5, 9]);
+        assert_eq!(registry.get(5).unwrap().solve(1, "").unwrap(), 5);
+        assert!(registry.get(3).is_none());
+    }
+
+    #[test]
+    fn test_registry_replaces_duplicate_day() {
+        let mut registry = Registry::new();
+
+        registry.register(&Fake(2));
+        registry.register(&Fake(2));
+
+        assert_eq!(registry.days(), vec![2]);
+    }
+
+    #[test]
+    fn test_measure_times_each_phase() {
+        let pause = Duration::from_millis(10);
+
+        let solution = Solution::measure(
+            "123",
+            |input| {
+                std::thread::sleep(pause);
+                Ok(input.parse::<i64>().unwrap())
+            },
+            |number| Ok(Answer::from(number)),
+        )
+        .unwrap();
+
+        assert_eq!(solution.answer, 123);
+        assert!(solution.timings.parse >= pause);
+        assert!(solution.timings.solve < pause);
+        assert_eq!(
+            solution.timings.total(),
+            solution.timings.parse + solution.timings.solve
+        );
+    }
+
+    #[test]
+    fn test_all_days_registered() {
+        let registry = crate::registry();
+
+        for solver in registry.iter() {
+            assert!(!solver.parts().is_empty());
+            assert!(solver.parts().iter().all(|&part| solver.supports(part)));
+        }
+
+        // Every `day_NN` module, as created by `new`, should be registered.
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut days = std::fs::read_dir(src)
+            .unwrap()
+            .filter_map(|entry| {
+                let name = entry.unwrap().file_name().into_string().ok()?;
+
+                name.strip_prefix("day_")?.strip_suffix(".rs")?.parse().ok()
+            })
+            .collect::<Vec<u8>>();
+        days.sort();
+
+        assert!(!days.is_empty());
+        assert_eq!(registry.days(), days);
+    }
+
+    #[test]
+    fn test_examples() {
+        for solver in crate::registry().iter() {
+            let examples = solver.examples();
+
+            for example in examples {
+                assert!(!example.answers.is_empty());
+                assert!(std::ptr::eq(
+                    Example::find(examples, example.name).unwrap(),
+                    example
+                ));
+
+                for &(part, expected) in example.answers {
+                    assert_eq!(
+                        solver.solve(part, example.input).unwrap(),
+                        Answer::from(expected),
+                        "day {} part {} example {}",
+                        solver.day(),
+                        part,
+                        example.name
+                    );
+                }
+            }
+        }
+    }
+}
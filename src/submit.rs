@@ -0,0 +1,388 @@
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+
+/// The default location of the submission log.
+pub const DEFAULT_LOG_PATH: &str = "submissions.log";
+
+/// The default number of seconds to wait between submissions.
+pub const DEFAULT_COOLDOWN: u64 = 60;
+
+/// What the puzzle site said about a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+    /// The response did not match anything we know how to read.
+    Unknown,
+}
+
+impl Feedback {
+    /// Whether the answer is known to be wrong and should never be submitted
+    /// again.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::Wrong | Self::TooHigh | Self::TooLow)
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Correct => "that's the right answer",
+            Self::Wrong => "that's not the right answer",
+            Self::TooHigh => "that's not the right answer, it is too high",
+            Self::TooLow => "that's not the right answer, it is too low",
+            Self::RateLimited => "an answer was submitted too recently",
+            Self::AlreadySolved => "this part is already solved",
+            Self::Unknown => "the response could not be understood",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+/// The puzzle site's reply to a submission.
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    pub feedback: Feedback,
+    /// How many seconds the site asked us to wait before submitting again.
+    pub wait: Option<u64>,
+}
+
+impl Reply {
+    /// Reads the reply out of the HTML page returned for a submission.
+    pub fn parse(body: &str) -> Self {
+        let text = body.to_lowercase();
+
+        let feedback = if text.contains("that's the right answer") {
+            Feedback::Correct
+        } else if text.contains("answer too recently") {
+            Feedback::RateLimited
+        } else if text.contains("don't seem to be solving the right level") {
+            Feedback::AlreadySolved
+        } else if text.contains("your answer is too high") {
+            Feedback::TooHigh
+        } else if text.contains("your answer is too low") {
+            Feedback::TooLow
+        } else if text.contains("not the right answer") {
+            Feedback::Wrong
+        } else {
+            Feedback::Unknown
+        };
+
+        Self {
+            feedback,
+            wait: parse_wait(&text),
+        }
+    }
+}
+
+/// Finds a wait like "you have 4m 3s left to wait" or "please wait one
+/// minute before trying again", in seconds.
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("you have ")? + "you have ".len();
+
+        return text[start..end]
+            .split_ascii_whitespace()
+            .map(
+                |amount| match amount.split_at(amount.len().saturating_sub(1)) {
+                    (n, "h") => n.parse::<u64>().ok().map(|n| n * 3600),
+                    (n, "m") => n.parse::<u64>().ok().map(|n| n * 60),
+                    (n, "s") => n.parse::<u64>().ok(),
+                    _ => None,
+                },
+            )
+            .sum();
+    }
+
+    let start = text.find("please wait ")? + "please wait ".len();
+    let mut words = text[start..].split_ascii_whitespace();
+
+    let count = match words.next()? {
+        "one" => 1,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("second") => Some(count),
+        unit if unit.starts_with("minute") => Some(count * 60),
+        _ => None,
+    }
+}
+
+/// One submission and what came of it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub feedback: Feedback,
+    /// When the answer was submitted, in seconds since the Unix epoch.
+    pub at: u64,
+    /// How many seconds the site asked us to wait afterwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<u64>,
+}
+
+/// Why an answer will not be submitted.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadySolved,
+    AlreadyTried(Feedback),
+    /// An earlier answer already rules this one out.
+    OutOfBounds {
+        bound: Answer,
+        feedback: Feedback,
+    },
+    CoolingDown {
+        remaining: u64,
+    },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadySolved => write!(f, "this part has already been solved"),
+            Self::AlreadyTried(feedback) => {
+                write!(f, "this answer was already submitted: {}", feedback)
+            }
+            Self::OutOfBounds { bound, feedback } => {
+                write!(f, "{} was already submitted and {}", bound, feedback)
+            }
+            Self::CoolingDown { remaining } => {
+                write!(f, "wait {}s before submitting again", remaining)
+            }
+        }
+    }
+}
+
+/// An append-only record of every submission, one JSON object per line.
+#[derive(Debug, Default)]
+pub struct Log {
+    attempts: Vec<Attempt>,
+}
+
+impl Log {
+    /// Loads the log, treating a missing file as an empty log.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("could not read submission log {}: {}", path, e)),
+        };
+
+        let attempts = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    format!("invalid submission log {} on line {}: {}", path, i + 1, e)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { attempts })
+    }
+
+    /// Records an attempt in memory and on disk.
+    pub fn append(&mut self, path: &str, attempt: Attempt) -> Result<(), String> {
+        let line = serde_json::to_string(&attempt).map_err(|e| e.to_string())?;
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|e| format!("could not write submission log {}: {}", path, e))?;
+
+        self.attempts.push(attempt);
+
+        Ok(())
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Decides whether an answer may be submitted at time `now`, given at
+    /// least `cooldown` seconds must pass between any two submissions.
+    pub fn refusal(
+        &self,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+        now: u64,
+        cooldown: u64,
+    ) -> Option<Refusal> {
+        let previous = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part);
+
+        for attempt in previous {
+            match attempt.feedback {
+                Feedback::Correct | Feedback::AlreadySolved => return Some(Refusal::AlreadySolved),
+                feedback if feedback.is_wrong() && attempt.answer == *answer => {
+                    return Some(Refusal::AlreadyTried(feedback))
+                }
+                Feedback::TooHigh | Feedback::TooLow => {
+                    let ruled_out = match attempt.feedback {
+                        Feedback::TooHigh => answer >= &attempt.answer,
+                        _ => answer <= &attempt.answer,
+                    };
+
+                    if ruled_out {
+                        return Some(Refusal::OutOfBounds {
+                            bound: attempt.answer.clone(),
+                            feedback: attempt.feedback,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let ready_at = self
+            .attempts
+            .iter()
+            .map(|attempt| attempt.at + attempt.wait.unwrap_or(0).max(cooldown))
+            .max()?;
+
+        (now < ready_at).then(|| Refusal::CoolingDown {
+            remaining: ready_at - now,
+        })
+    }
+}
+
+/// The current time in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn test_parse_replies() {
+        let replies = [
+            ("That's the right answer! You are one gold star closer.", Feedback::Correct, None),
+            ("That's not the right answer; your answer is too high. Please wait one minute before trying again.", Feedback::TooHigh, Some(60)),
+            ("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.", Feedback::TooLow, Some(300)),
+            ("That's not the right answer. If you're stuck, make sure you're using the full input data.", Feedback::Wrong, None),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 3s left to wait.", Feedback::RateLimited, Some(243)),
+            ("You don't seem to be solving the right level. Did you already complete it?", Feedback::AlreadySolved, None),
+            ("Something else entirely.", Feedback::Unknown, None),
+        ];
+
+        for (message, feedback, wait) in replies {
+            assert_eq!(
+                Reply::parse(&page(message)),
+                Reply { feedback, wait },
+                "{}",
+                message
+            );
+        }
+    }
+
+    fn attempt(part: u8, answer: i64, feedback: Feedback, at: u64) -> Attempt {
+        Attempt {
+            day: 6,
+            part,
+            answer: Answer::from(answer),
+            feedback,
+            at,
+            wait: None,
+        }
+    }
+
+    fn log(attempts: Vec<Attempt>) -> Log {
+        Log { attempts }
+    }
+
+    #[test]
+    fn test_never_resubmits_wrong_answers() {
+        let log = log(vec![
+            attempt(1, 100, Feedback::Wrong, 0),
+            attempt(1, 500, Feedback::TooHigh, 0),
+            attempt(1, 20, Feedback::TooLow, 0),
+        ]);
+
+        let refusal = |answer: i64| log.refusal(6, 1, &Answer::from(answer), 1000, 60);
+
+        assert_eq!(refusal(100), Some(Refusal::AlreadyTried(Feedback::Wrong)));
+        assert_eq!(refusal(20), Some(Refusal::AlreadyTried(Feedback::TooLow)));
+        assert!(matches!(refusal(600), Some(Refusal::OutOfBounds { .. })));
+        assert!(matches!(refusal(3), Some(Refusal::OutOfBounds { .. })));
+        assert_eq!(refusal(250), None);
+        assert_eq!(log.refusal(6, 2, &Answer::from(100i64), 1000, 60), None);
+    }
+
+    #[test]
+    fn test_refuses_solved_parts() {
+        let log = log(vec![attempt(1, 7, Feedback::Correct, 0)]);
+
+        assert_eq!(
+            log.refusal(6, 1, &Answer::from(8i64), 1000, 60),
+            Some(Refusal::AlreadySolved)
+        );
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let mut rate_limited = attempt(2, 9, Feedback::RateLimited, 1000);
+        rate_limited.wait = Some(300);
+
+        let log = log(vec![attempt(1, 7, Feedback::Wrong, 900), rate_limited]);
+        let answer = Answer::from(8i64);
+
+        assert_eq!(
+            log.refusal(6, 1, &answer, 1030, 60),
+            Some(Refusal::CoolingDown { remaining: 270 })
+        );
+        assert_eq!(log.refusal(6, 1, &answer, 1300, 60), None);
+        assert_eq!(log.refusal(6, 2, &Answer::from(9i64), 1300, 60), None);
+    }
+
+    #[test]
+    fn test_log_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-submissions-{}.log", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let mut log = Log::load(path).unwrap();
+        log.append(path, attempt(1, 7, Feedback::TooLow, 5))
+            .unwrap();
+        log.append(path, attempt(1, 9, Feedback::Correct, 70))
+            .unwrap();
+
+        assert_eq!(Log::load(path).unwrap().attempts(), log.attempts());
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .starts_with(r#"{"day":6,"part":1,"answer":7,"feedback":"too_low","at":5}"#));
+    }
+}
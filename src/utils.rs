@@ -34,3 +34,112 @@ pub fn format_duration(duration: Duration) -> String {
         format!("{} ns", nanos)
     }
 }
+
+/// Formats a number of bytes with the largest binary unit that keeps it at
+/// or above one.
+///
+/// ```
+/// # use adventofcode2023::utils::format_bytes;
+///
+/// assert_eq!("512 B", &format_bytes(512));
+/// assert_eq!("1.50 KiB", &format_bytes(1536));
+/// assert_eq!("2.00 MiB", &format_bytes(2 * 1024 * 1024));
+/// ```
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+/// Formats rows of cells as a plain-text table with a header.
+///
+/// Every column is padded to the width of its widest cell.
+///
+/// ```
+/// # use adventofcode2023::utils::format_table;
+///
+/// let rows = [
+///     ["1".to_string(), "55386".to_string()],
+///     ["19".to_string(), "489392".to_string()],
+/// ];
+///
+/// let expected = "\
+/// Day  Answer
+/// ---  ------
+/// 1    55386
+/// 19   489392";
+///
+/// assert_eq!(expected, &format_table(["Day", "Answer"], &rows));
+/// ```
+pub fn format_table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = headers.map(|header| header.chars().count());
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: [&str; N]| {
+        cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let rule = widths.map(|width| "-".repeat(width));
+
+    let mut lines = vec![
+        format_row(headers),
+        format_row(rule.each_ref().map(String::as_str)),
+    ];
+
+    lines.extend(
+        rows.iter()
+            .map(|row| format_row(row.each_ref().map(String::as_str))),
+    );
+
+    lines.join("\n")
+}
+
+/// Counts the grid points inside a closed loop that runs along grid lines,
+/// given its vertices in order, and the points on the loop itself.
+///
+/// The area comes from the shoelace formula and the inside points from Pick's
+/// theorem, so the loop can be far too large to fill.
+///
+/// ```
+/// # use adventofcode2023::utils::lattice_points;
+///
+/// // A 3x2 rectangle has 2x1 points inside and 10 on its edges.
+/// assert_eq!((2, 10), lattice_points(&[(0, 0), (3, 0), (3, 2), (0, 2)]));
+/// ```
+pub fn lattice_points(vertices: &[(i64, i64)]) -> (i64, i64) {
+    let mut area = 0;
+    let mut boundary = 0;
+
+    for (i, &(x1, y1)) in vertices.iter().enumerate() {
+        let (x2, y2) = vertices[(i + 1) % vertices.len()];
+
+        area += x1 * y2 - x2 * y1;
+        boundary += (x2 - x1).abs() + (y2 - y1).abs();
+    }
+
+    (area.abs() / 2 - boundary / 2 + 1, boundary)
+}
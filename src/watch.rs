@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// How often watched files are checked unless another interval is given, in
+/// milliseconds.
+pub const DEFAULT_INTERVAL: u64 = 500;
+
+/// What a file looked like when it was last checked. The length catches
+/// rewrites that land within the resolution of the modification time.
+type Stamp = Option<(SystemTime, u64)>;
+
+/// Notices changes to a set of files by polling their metadata, so no
+/// filesystem notification service is needed.
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    /// Starts watching the files as they are now.
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let stamp = stamp(&path);
+                    (path, stamp)
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether any file was modified, created or removed since the last check.
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+
+        for (path, last) in &mut self.files {
+            let current = stamp(path);
+
+            if current != *last {
+                *last = current;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Blocks until a file changes, checking every `interval`.
+    pub fn wait(&mut self, interval: Duration) {
+        while !self.changed() {
+            std::thread::sleep(interval);
+        }
+    }
+}
+
+fn stamp(path: &PathBuf) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        std::fs::write(&path, "Time: 7\n").unwrap();
+
+        let mut watcher = Watcher::new([path.clone()]);
+
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "Time: 7 15\n").unwrap();
+
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(watcher.changed());
+    }
+}